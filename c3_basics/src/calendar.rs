@@ -0,0 +1,103 @@
+////////////////////////////////////////////////////////////////////////////////
+// Months of the year
+////////////////////////////////////////////////////////////////////////////////
+// arr() says the months of the year are a good fit for an array because
+// that size will never change. Here it is written out.
+//
+// NAMES is a plain [&str; 12]. The 12 is part of the type, so the compiler
+// knows the length without ever looking at the data.
+//
+// Table<T, N> takes that further with *const generics*: N is a value
+// (not a type) that the type is generic over, so a Table<u32, 12> and a
+// Table<u32, 7> are different types. Only a 12 entry table can be indexed
+// by Month, and that's checked at compile time rather than at runtime.
+////////////////////////////////////////////////////////////////////////////////
+use std::ops::Index;
+use std::slice;
+
+pub const NAMES: [&str; 12] = [
+  "January", "February", "March", "April", "May", "June",
+  "July", "August", "September", "October", "November", "December",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Month {
+  January, February, March, April, May, June,
+  July, August, September, October, November, December,
+}
+
+impl Month {
+  pub const ALL: [Month; 12] = [
+    Month::January, Month::February, Month::March, Month::April,
+    Month::May, Month::June, Month::July, Month::August,
+    Month::September, Month::October, Month::November, Month::December,
+  ];
+
+  pub fn name(self) -> &'static str {
+    NAMES[self as usize]
+  }
+
+  pub fn days(self, year: u32) -> u32 {
+    match self {
+      Month::February if is_leap_year(year) => 29,
+      Month::February => 28,
+      Month::April | Month::June | Month::September | Month::November => 30,
+      _ => 31,
+    }
+  }
+}
+
+// Every fourth year, except centuries, except every fourth century.
+// 2000 was a leap year, 1900 wasn't.
+pub fn is_leap_year(year: u32) -> bool {
+  (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+pub struct Table<T, const N: usize> {
+  items: [T; N],
+}
+
+impl<T, const N: usize> Table<T, N> {
+  pub fn new(items: [T; N]) -> Table<T, N> {
+    Table { items }
+  }
+
+  // No need to store the length anywhere, it's right there in the type.
+  pub fn len(&self) -> usize {
+    N
+  }
+
+  pub fn is_empty(&self) -> bool {
+    N == 0
+  }
+
+  pub fn iter(&self) -> slice::Iter<'_, T> {
+    self.items.iter()
+  }
+}
+
+impl<T, const N: usize> Index<usize> for Table<T, N> {
+  type Output = T;
+
+  fn index(&self, idx: usize) -> &T {
+    &self.items[idx]
+  }
+}
+
+// The N here is fixed at 12, so this only exists for Table<T, 12>.
+// Indexing a Table<T, 7> with a Month is a compile error.
+impl<T> Index<Month> for Table<T, 12> {
+  type Output = T;
+
+  fn index(&self, month: Month) -> &T {
+    &self.items[month as usize]
+  }
+}
+
+pub fn days_in(year: u32) -> Table<u32, 12> {
+  let mut days = [0; 12];
+  for (slot, month) in days.iter_mut().zip(Month::ALL.iter()) {
+    *slot = month.days(year);
+  }
+  Table::new(days)
+}
@@ -0,0 +1,222 @@
+////////////////////////////////////////////////////////////////////////////////
+// Countdown
+////////////////////////////////////////////////////////////////////////////////
+// The "3! 2! 1! GO" from loops() uses (1..4).rev(). Range is just a type
+// from the stdlib that implements the Iterator trait, so we can write our
+// own type that does the same job, with a few more knobs:
+//
+//   Countdown::new(10).step(2).tick(Duration::from_millis(250))
+//
+// counts 10, 8, 6, 4, 2 with a quarter second between each.
+//
+// Iterator only needs next(). DoubleEndedIterator adds next_back(), which
+// is all rev() needs. ExactSizeIterator promises size_hint() is exact,
+// which gives us len() for free.
+//
+// Actually waiting between ticks goes through the Clock trait. The real
+// one sleeps, the fake one just adds up how long it would have slept, so
+// a 10 second countdown can be checked instantly.
+////////////////////////////////////////////////////////////////////////////////
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+pub struct Countdown {
+  start: u32,
+  step: u32,
+  tick: Duration,
+  // Items are numbered from 0 at `start`. front is the next one next()
+  // hands out, back is one past the next one next_back() hands out.
+  front: u32,
+  back: u32,
+}
+
+impl Countdown {
+  pub fn new(start: u32) -> Countdown {
+    Countdown { start, step: 1, tick: Duration::from_secs(1), front: 0, back: start }
+  }
+
+  pub fn step(mut self, step: u32) -> Countdown {
+    assert!(step > 0, "a countdown step of 0 would never finish");
+    self.step = step;
+    // Everything above 0: start, start - step, ... down to the last one
+    // that's still positive.
+    self.back = if self.start == 0 { 0 } else { (self.start - 1) / step + 1 };
+    self
+  }
+
+  pub fn tick(mut self, tick: Duration) -> Countdown {
+    self.tick = tick;
+    self
+  }
+
+  fn nth_value(&self, idx: u32) -> u32 {
+    self.start - idx * self.step
+  }
+
+  // Counts down with a tick between each number, calling on_tick with
+  // how long it's been since the countdown started and the number.
+  pub fn launch<C, F>(self, clock: &mut C, mut on_tick: F) -> Duration
+    where C: Clock, F: FnMut(Duration, u32) {
+    let started = clock.now();
+    let tick = self.tick;
+    for (i, n) in self.enumerate() {
+      if i > 0 {
+        clock.sleep(tick);
+      }
+      on_tick(clock.now() - started, n);
+    }
+    clock.sleep(tick);
+    clock.now() - started
+  }
+}
+
+impl Iterator for Countdown {
+  type Item = u32;
+
+  fn next(&mut self) -> Option<u32> {
+    if self.front == self.back {
+      return None;
+    }
+    let value = self.nth_value(self.front);
+    self.front += 1;
+    Some(value)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = (self.back - self.front) as usize;
+    (len, Some(len))
+  }
+}
+
+impl DoubleEndedIterator for Countdown {
+  fn next_back(&mut self) -> Option<u32> {
+    if self.front == self.back {
+      return None;
+    }
+    self.back -= 1;
+    Some(self.nth_value(self.back))
+  }
+}
+
+impl ExactSizeIterator for Countdown {}
+
+pub trait Clock {
+  // Time since the clock was made.
+  fn now(&self) -> Duration;
+  fn sleep(&mut self, duration: Duration);
+}
+
+pub struct RealClock {
+  started: Instant,
+}
+
+impl RealClock {
+  pub fn new() -> RealClock {
+    RealClock { started: Instant::now() }
+  }
+}
+
+impl Default for RealClock {
+  fn default() -> RealClock {
+    RealClock::new()
+  }
+}
+
+impl Clock for RealClock {
+  fn now(&self) -> Duration {
+    self.started.elapsed()
+  }
+
+  fn sleep(&mut self, duration: Duration) {
+    thread::sleep(duration);
+  }
+}
+
+#[derive(Default)]
+pub struct FakeClock {
+  elapsed: Duration,
+}
+
+impl FakeClock {
+  pub fn new() -> FakeClock {
+    FakeClock { elapsed: Duration::from_secs(0) }
+  }
+}
+
+impl Clock for FakeClock {
+  fn now(&self) -> Duration {
+    self.elapsed
+  }
+
+  fn sleep(&mut self, duration: Duration) {
+    self.elapsed += duration;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn launch_ticks_on_the_fake_clock() {
+    let mut clock = FakeClock::new();
+    let mut ticks = Vec::new();
+    let total = Countdown::new(10).step(2).tick(Duration::from_millis(250))
+      .launch(&mut clock, |at, n| ticks.push((at, n)));
+
+    let ms = Duration::from_millis;
+    assert_eq!(ticks, [(ms(0), 10), (ms(250), 8), (ms(500), 6), (ms(750), 4), (ms(1000), 2)]);
+    // One more tick after the last number, for the GO.
+    assert_eq!(total, ms(1250));
+    assert_eq!(clock.now(), total);
+  }
+
+  #[test]
+  fn launch_of_nothing_still_waits_for_go() {
+    let mut clock = FakeClock::new();
+    let mut ticks = 0;
+    let total = Countdown::new(0).launch(&mut clock, |_, _| ticks += 1);
+    assert_eq!(ticks, 0);
+    assert_eq!(total, Duration::from_secs(1));
+  }
+
+  #[test]
+  fn next_and_next_back_meet_in_the_middle() {
+    let mut countdown = Countdown::new(5);
+    assert_eq!(countdown.next(), Some(5));
+    assert_eq!(countdown.next_back(), Some(1));
+    assert_eq!(countdown.next(), Some(4));
+    assert_eq!(countdown.next_back(), Some(2));
+    assert_eq!(countdown.next(), Some(3));
+    assert_eq!(countdown.next(), None);
+    assert_eq!(countdown.next_back(), None);
+  }
+
+  #[test]
+  fn len_after_partial_consumption() {
+    let mut countdown = Countdown::new(10).step(2);
+    assert_eq!(countdown.len(), 5);
+    countdown.next();
+    assert_eq!(countdown.len(), 4);
+    countdown.next_back();
+    assert_eq!(countdown.len(), 3);
+    assert_eq!(countdown.collect::<Vec<_>>(), [8, 6, 4]);
+  }
+
+  #[test]
+  fn step_bigger_than_start() {
+    let countdown = Countdown::new(3).step(5);
+    assert_eq!(countdown.len(), 1);
+    assert_eq!(countdown.rev().collect::<Vec<_>>(), [3]);
+  }
+
+  #[test]
+  fn new_zero_is_empty() {
+    let mut countdown = Countdown::new(0);
+    assert_eq!(countdown.len(), 0);
+    assert_eq!(countdown.next(), None);
+    assert_eq!(countdown.next_back(), None);
+    assert_eq!(Countdown::new(0).step(3).len(), 0);
+  }
+}
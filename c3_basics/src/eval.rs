@@ -0,0 +1,933 @@
+////////////////////////////////////////////////////////////////////////////////
+// Expressions vs statements
+////////////////////////////////////////////////////////////////////////////////
+// A tiny evaluator for a Rust-like subset, so the statement vs expression
+// rules from the functions notes can be poked at directly. It knows about:
+//
+//   let x = 5;   let y: bool = true;
+//   { ... }      blocks, evaluating to their last expression
+//   if c { .. } else { .. }
+//   + - * / %    on i32, == != < > <= >= && || ! on the usual suspects
+//   fn name(a: i32) -> i32 { ... }   and calls to those fns
+//
+// Like rustc, every statement is type checked before it runs, so an `if`
+// with mismatched arms is an error even if the bad arm would never be
+// taken. Unlike rustc, a line is checked and run one statement at a
+// time, the way a REPL would: `let a = 1; let b = a + true;` binds a
+// before reporting the error in b. Each statement or expression prints
+// what it evaluated to:
+//
+//   x + 1    -> 6: i32
+//   x + 1;   -> (), the `;` threw away 6: i32
+//   let y = 6;  is a statement and doesn't evaluate to anything at all
+////////////////////////////////////////////////////////////////////////////////
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ty {
+  I32,
+  Bool,
+  Unit,
+}
+
+impl fmt::Display for Ty {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Ty::I32 => write!(f, "i32"),
+      Ty::Bool => write!(f, "bool"),
+      Ty::Unit => write!(f, "()"),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+  I32(i32),
+  Bool(bool),
+  Unit,
+}
+
+impl Value {
+  fn ty(&self) -> Ty {
+    match *self {
+      Value::I32(_) => Ty::I32,
+      Value::Bool(_) => Ty::Bool,
+      Value::Unit => Ty::Unit,
+    }
+  }
+}
+
+impl fmt::Display for Value {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Value::I32(n) => write!(f, "{}", n),
+      Value::Bool(b) => write!(f, "{}", b),
+      Value::Unit => write!(f, "()"),
+    }
+  }
+}
+
+// Compile errors read like rustc's, runtime failures like a panic.
+#[derive(Debug)]
+pub struct Error {
+  header: String,
+  notes: Vec<String>,
+}
+
+impl Error {
+  fn syntax(msg: String) -> Error {
+    Error { header: format!("error: {}", msg), notes: Vec::new() }
+  }
+
+  fn compile(code: &str, msg: &str) -> Error {
+    Error { header: format!("error[{}]: {}", code, msg), notes: Vec::new() }
+  }
+
+  fn mismatch(expected: Ty, found: Ty) -> Error {
+    Error::compile("E0308", "mismatched types")
+      .note(format!("note: expected `{}`, found `{}`", expected, found))
+  }
+
+  fn panic(msg: &str) -> Error {
+    Error { header: format!("panicked: {}", msg), notes: Vec::new() }
+  }
+
+  fn note(mut self, note: String) -> Error {
+    self.notes.push(note);
+    self
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.header)?;
+    for note in &self.notes {
+      write!(f, "\n  = {}", note)?;
+    }
+    Ok(())
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Lexing
+////////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+  Int(i32),
+  Ident(String),
+  Let, Fn, If, Else, True, False,
+  Plus, Minus, Star, Slash, Percent,
+  EqEq, NotEq, Lt, Gt, Le, Ge, AndAnd, OrOr, Bang,
+  Eq, Semi, Colon, Comma, Arrow,
+  LParen, RParen, LBrace, RBrace,
+  Eof,
+}
+
+impl fmt::Display for Tok {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let s = match *self {
+      Tok::Int(n) => return write!(f, "`{}`", n),
+      Tok::Ident(ref name) => return write!(f, "`{}`", name),
+      Tok::Eof => return write!(f, "<eof>"),
+      Tok::Let => "let", Tok::Fn => "fn", Tok::If => "if", Tok::Else => "else",
+      Tok::True => "true", Tok::False => "false",
+      Tok::Plus => "+", Tok::Minus => "-", Tok::Star => "*", Tok::Slash => "/",
+      Tok::Percent => "%", Tok::EqEq => "==", Tok::NotEq => "!=", Tok::Lt => "<",
+      Tok::Gt => ">", Tok::Le => "<=", Tok::Ge => ">=", Tok::AndAnd => "&&",
+      Tok::OrOr => "||", Tok::Bang => "!", Tok::Eq => "=", Tok::Semi => ";",
+      Tok::Colon => ":", Tok::Comma => ",", Tok::Arrow => "->",
+      Tok::LParen => "(", Tok::RParen => ")", Tok::LBrace => "{", Tok::RBrace => "}",
+    };
+    write!(f, "`{}`", s)
+  }
+}
+
+// Each token remembers where it starts and ends in the source, so a
+// statement can be echoed back exactly as it was typed.
+fn lex(src: &str) -> Result<Vec<(Tok, usize, usize)>, Error> {
+  let bytes = src.as_bytes();
+  let mut toks = Vec::new();
+  let mut i = 0;
+  while i < bytes.len() {
+    let c = bytes[i] as char;
+    let start = i;
+    if c.is_whitespace() {
+      i += 1;
+      continue;
+    }
+
+    if c.is_ascii_digit() {
+      while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'_') {
+        i += 1;
+      }
+      let digits: String = src[start..i].chars().filter(|c| *c != '_').collect();
+      let n = digits.parse::<i32>().map_err(|_| {
+        Error::syntax("literal out of range for `i32`".to_string())
+      })?;
+      toks.push((Tok::Int(n), start, i));
+      continue;
+    }
+
+    if c.is_ascii_alphabetic() || c == '_' {
+      while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+        i += 1;
+      }
+      let tok = match &src[start..i] {
+        "let" => Tok::Let,
+        "fn" => Tok::Fn,
+        "if" => Tok::If,
+        "else" => Tok::Else,
+        "true" => Tok::True,
+        "false" => Tok::False,
+        word => Tok::Ident(word.to_string()),
+      };
+      toks.push((tok, start, i));
+      continue;
+    }
+
+    let two = src.get(i..i + 2).unwrap_or("");
+    let tok = match two {
+      "==" => Some(Tok::EqEq),
+      "!=" => Some(Tok::NotEq),
+      "<=" => Some(Tok::Le),
+      ">=" => Some(Tok::Ge),
+      "&&" => Some(Tok::AndAnd),
+      "||" => Some(Tok::OrOr),
+      "->" => Some(Tok::Arrow),
+      _ => None,
+    };
+    if let Some(tok) = tok {
+      i += 2;
+      toks.push((tok, start, i));
+      continue;
+    }
+
+    let tok = match c {
+      '+' => Tok::Plus, '-' => Tok::Minus, '*' => Tok::Star, '/' => Tok::Slash,
+      '%' => Tok::Percent, '<' => Tok::Lt, '>' => Tok::Gt, '!' => Tok::Bang,
+      '=' => Tok::Eq, ';' => Tok::Semi, ':' => Tok::Colon, ',' => Tok::Comma,
+      '(' => Tok::LParen, ')' => Tok::RParen, '{' => Tok::LBrace, '}' => Tok::RBrace,
+      _ => {
+        let c = src[i..].chars().next().unwrap_or(c);
+        return Err(Error::syntax(format!("unknown start of token: {}", c)));
+      },
+    };
+    i += 1;
+    toks.push((tok, start, i));
+  }
+  toks.push((Tok::Eof, src.len(), src.len()));
+  Ok(toks)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Parsing
+////////////////////////////////////////////////////////////////////////////////
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinOp {
+  Add, Sub, Mul, Div, Rem,
+  Eq, Ne, Lt, Gt, Le, Ge,
+  And, Or,
+}
+
+impl BinOp {
+  // For the "attempt to add with overflow" style panics.
+  fn verb(&self) -> &'static str {
+    match *self {
+      BinOp::Add => "add", BinOp::Sub => "subtract", BinOp::Mul => "multiply",
+      BinOp::Div => "divide", BinOp::Rem => "calculate the remainder",
+      _ => "compare",
+    }
+  }
+
+  // What rustc says when the left hand side can't do arithmetic at all.
+  fn cannot(&self, lhs: Ty, rhs: Ty) -> String {
+    match *self {
+      BinOp::Add => format!("cannot add `{}` to `{}`", rhs, lhs),
+      BinOp::Sub => format!("cannot subtract `{}` from `{}`", rhs, lhs),
+      BinOp::Mul => format!("cannot multiply `{}` by `{}`", lhs, rhs),
+      BinOp::Div => format!("cannot divide `{}` by `{}`", lhs, rhs),
+      _ => format!("cannot calculate the remainder of `{}` divided by `{}`", lhs, rhs),
+    }
+  }
+}
+
+#[derive(Debug)]
+enum Expr {
+  Lit(Value),
+  Var(String),
+  Call(String, Vec<Expr>),
+  Neg(Box<Expr>),
+  Not(Box<Expr>),
+  Binary(BinOp, Box<Expr>, Box<Expr>),
+  Block(Block),
+  If(Box<Expr>, Block, Option<Box<Expr>>),
+}
+
+impl Expr {
+  // Blocks and ifs can stand on their own as statements without a `;`,
+  // as long as they evaluate to ().
+  fn is_block_like(&self) -> bool {
+    matches!(*self, Expr::Block(_) | Expr::If(..))
+  }
+}
+
+#[derive(Debug)]
+struct FnDef {
+  name: String,
+  params: Vec<(String, Ty)>,
+  ret: Ty,
+  body: Block,
+}
+
+#[derive(Debug)]
+enum StmtKind {
+  Let(String, Option<Ty>, Expr),
+  Expr(Expr, bool),
+  Fn(Rc<FnDef>),
+}
+
+#[derive(Debug)]
+struct Stmt {
+  kind: StmtKind,
+  start: usize,
+  end: usize,
+}
+
+#[derive(Debug)]
+struct Block {
+  stmts: Vec<Stmt>,
+  tail: Option<(Box<Expr>, usize, usize)>,
+}
+
+struct Parser {
+  toks: Vec<(Tok, usize, usize)>,
+  pos: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> &Tok {
+    &self.toks[self.pos].0
+  }
+
+  fn start(&self) -> usize {
+    self.toks[self.pos].1
+  }
+
+  fn last_end(&self) -> usize {
+    if self.pos == 0 { 0 } else { self.toks[self.pos - 1].2 }
+  }
+
+  fn bump(&mut self) -> Tok {
+    let tok = self.toks[self.pos].0.clone();
+    if tok != Tok::Eof {
+      self.pos += 1;
+    }
+    tok
+  }
+
+  fn eat(&mut self, tok: &Tok) -> bool {
+    if self.peek() == tok {
+      self.bump();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, tok: Tok) -> Result<(), Error> {
+    if self.eat(&tok) {
+      Ok(())
+    } else {
+      Err(Error::syntax(format!("expected {}, found {}", tok, self.peek())))
+    }
+  }
+
+  fn ident(&mut self) -> Result<String, Error> {
+    match self.bump() {
+      Tok::Ident(name) => Ok(name),
+      tok => Err(Error::syntax(format!("expected identifier, found {}", tok))),
+    }
+  }
+
+  fn ty(&mut self) -> Result<Ty, Error> {
+    if self.eat(&Tok::LParen) {
+      self.expect(Tok::RParen)?;
+      return Ok(Ty::Unit);
+    }
+    match self.ident()?.as_str() {
+      "i32" => Ok(Ty::I32),
+      "bool" => Ok(Ty::Bool),
+      other => Err(Error::compile("E0412", &format!("cannot find type `{}` in this scope", other))),
+    }
+  }
+
+  // Statements up to `end`, with an optional trailing expression that
+  // becomes the value of the whole block.
+  fn block_body(&mut self, end: &Tok) -> Result<Block, Error> {
+    let mut stmts = Vec::new();
+    loop {
+      while self.eat(&Tok::Semi) {}
+      if self.peek() == end {
+        return Ok(Block { stmts, tail: None });
+      }
+
+      let start = self.start();
+      if self.eat(&Tok::Let) {
+        let name = self.ident()?;
+        let ty = if self.eat(&Tok::Colon) { Some(self.ty()?) } else { None };
+        self.expect(Tok::Eq)?;
+        let value = self.expr()?;
+        self.expect(Tok::Semi)?;
+        stmts.push(Stmt { kind: StmtKind::Let(name, ty, value), start, end: self.last_end() });
+        continue;
+      }
+
+      if self.eat(&Tok::Fn) {
+        let def = self.fn_def()?;
+        stmts.push(Stmt { kind: StmtKind::Fn(Rc::new(def)), start, end: self.last_end() });
+        continue;
+      }
+
+      // A statement that starts with `{` or `if` ends at its closing `}`,
+      // like in rustc, so `{ 5 } - 1` is the statement `{ 5 }` followed by
+      // `-1`, not a subtraction. Wrap it in parens to use it as a value.
+      let expr = match *self.peek() {
+        Tok::LBrace | Tok::If => self.primary()?,
+        _ => self.expr()?,
+      };
+      if self.eat(&Tok::Semi) {
+        stmts.push(Stmt { kind: StmtKind::Expr(expr, true), start, end: self.last_end() });
+      } else if self.peek() == end {
+        let tail_end = self.last_end();
+        return Ok(Block { stmts, tail: Some((Box::new(expr), start, tail_end)) });
+      } else if expr.is_block_like() {
+        stmts.push(Stmt { kind: StmtKind::Expr(expr, false), start, end: self.last_end() });
+      } else {
+        return Err(Error::syntax(format!("expected `;`, found {}", self.peek())));
+      }
+    }
+  }
+
+  fn fn_def(&mut self) -> Result<FnDef, Error> {
+    let name = self.ident()?;
+    self.expect(Tok::LParen)?;
+    let mut params = Vec::new();
+    while !self.eat(&Tok::RParen) {
+      let param = self.ident()?;
+      self.expect(Tok::Colon)?;
+      params.push((param, self.ty()?));
+      if !self.eat(&Tok::Comma) {
+        self.expect(Tok::RParen)?;
+        break;
+      }
+    }
+    let ret = if self.eat(&Tok::Arrow) { self.ty()? } else { Ty::Unit };
+    self.expect(Tok::LBrace)?;
+    let body = self.block_body(&Tok::RBrace)?;
+    self.expect(Tok::RBrace)?;
+    Ok(FnDef { name, params, ret, body })
+  }
+
+  fn expr(&mut self) -> Result<Expr, Error> {
+    self.binary(0)
+  }
+
+  // Precedence climbing, loosest first: || then && then comparisons
+  // then + - then * / %.
+  fn binary(&mut self, level: usize) -> Result<Expr, Error> {
+    if level == 5 {
+      return self.unary();
+    }
+    let mut lhs = self.binary(level + 1)?;
+    loop {
+      let op = match (level, self.peek()) {
+        (0, &Tok::OrOr) => BinOp::Or,
+        (1, &Tok::AndAnd) => BinOp::And,
+        (2, &Tok::EqEq) => BinOp::Eq,
+        (2, &Tok::NotEq) => BinOp::Ne,
+        (2, &Tok::Lt) => BinOp::Lt,
+        (2, &Tok::Gt) => BinOp::Gt,
+        (2, &Tok::Le) => BinOp::Le,
+        (2, &Tok::Ge) => BinOp::Ge,
+        (3, &Tok::Plus) => BinOp::Add,
+        (3, &Tok::Minus) => BinOp::Sub,
+        (4, &Tok::Star) => BinOp::Mul,
+        (4, &Tok::Slash) => BinOp::Div,
+        (4, &Tok::Percent) => BinOp::Rem,
+        _ => return Ok(lhs),
+      };
+      self.bump();
+      let rhs = self.binary(level + 1)?;
+      lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+      // a == b == c is an error in Rust, comparisons don't chain.
+      if level == 2 {
+        return match *self.peek() {
+          Tok::EqEq | Tok::NotEq | Tok::Lt | Tok::Gt | Tok::Le | Tok::Ge =>
+            Err(Error::syntax("comparison operators cannot be chained".to_string())),
+          _ => Ok(lhs),
+        };
+      }
+    }
+  }
+
+  fn unary(&mut self) -> Result<Expr, Error> {
+    if self.eat(&Tok::Minus) {
+      return Ok(Expr::Neg(Box::new(self.unary()?)));
+    }
+    if self.eat(&Tok::Bang) {
+      return Ok(Expr::Not(Box::new(self.unary()?)));
+    }
+    self.primary()
+  }
+
+  fn primary(&mut self) -> Result<Expr, Error> {
+    match self.bump() {
+      Tok::Int(n) => Ok(Expr::Lit(Value::I32(n))),
+      Tok::True => Ok(Expr::Lit(Value::Bool(true))),
+      Tok::False => Ok(Expr::Lit(Value::Bool(false))),
+      Tok::Ident(name) => {
+        if !self.eat(&Tok::LParen) {
+          return Ok(Expr::Var(name));
+        }
+        let mut args = Vec::new();
+        while !self.eat(&Tok::RParen) {
+          args.push(self.expr()?);
+          if !self.eat(&Tok::Comma) {
+            self.expect(Tok::RParen)?;
+            break;
+          }
+        }
+        Ok(Expr::Call(name, args))
+      },
+      Tok::LParen => {
+        if self.eat(&Tok::RParen) {
+          return Ok(Expr::Lit(Value::Unit));
+        }
+        let inner = self.expr()?;
+        self.expect(Tok::RParen)?;
+        Ok(inner)
+      },
+      Tok::LBrace => {
+        let block = self.block_body(&Tok::RBrace)?;
+        self.expect(Tok::RBrace)?;
+        Ok(Expr::Block(block))
+      },
+      Tok::If => self.if_expr(),
+      // The C style `x = y = 6`: let is a statement, so there's nothing
+      // for the outer let to bind.
+      Tok::Let => Err(Error::syntax("expected expression, found `let` statement".to_string())
+        .note("note: variable declarations using `let` are statements".to_string())),
+      tok => Err(Error::syntax(format!("expected expression, found {}", tok))),
+    }
+  }
+
+  fn if_expr(&mut self) -> Result<Expr, Error> {
+    let cond = self.expr()?;
+    self.expect(Tok::LBrace)?;
+    let then = self.block_body(&Tok::RBrace)?;
+    self.expect(Tok::RBrace)?;
+    let otherwise = if !self.eat(&Tok::Else) {
+      None
+    } else if self.eat(&Tok::If) {
+      Some(Box::new(self.if_expr()?))
+    } else {
+      self.expect(Tok::LBrace)?;
+      let block = self.block_body(&Tok::RBrace)?;
+      self.expect(Tok::RBrace)?;
+      Some(Box::new(Expr::Block(block)))
+    };
+    Ok(Expr::If(Box::new(cond), then, otherwise))
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Checking and running
+////////////////////////////////////////////////////////////////////////////////
+type Scopes<T> = Vec<HashMap<String, T>>;
+
+fn lookup<'a, T>(scopes: &'a Scopes<T>, name: &str) -> Option<&'a T> {
+  scopes.iter().rev().filter_map(|scope| scope.get(name)).next()
+}
+
+// Deep enough for a recursive fib, shallow enough not to blow our stack.
+const MAX_DEPTH: usize = 256;
+
+pub struct Evaluator {
+  globals: HashMap<String, Value>,
+  fns: HashMap<String, Rc<FnDef>>,
+}
+
+impl Default for Evaluator {
+  fn default() -> Evaluator {
+    Evaluator::new()
+  }
+}
+
+impl Evaluator {
+  pub fn new() -> Evaluator {
+    Evaluator { globals: HashMap::new(), fns: HashMap::new() }
+  }
+
+  // Runs one line of input, printing each statement and the final
+  // expression (if there is one) along with what it evaluated to. Any
+  // `let`s and `fn`s stick around for the next line.
+  pub fn run(&mut self, src: &str) {
+    let block = match lex(src).and_then(|toks| Parser { toks, pos: 0 }.block_body(&Tok::Eof)) {
+      Ok(block) => block,
+      Err(e) => {
+        println!("{}\n{}", src, e);
+        return;
+      },
+    };
+
+    for stmt in &block.stmts {
+      let text = &src[stmt.start..stmt.end];
+      match self.run_stmt(stmt) {
+        Ok(report) => println!("{:<28} {}", text, report),
+        Err(e) => {
+          println!("{}\n{}", text, e);
+          return;
+        },
+      }
+    }
+
+    if let Some((ref expr, start, end)) = block.tail {
+      let text = &src[start..end];
+      let mut types = vec![self.global_types()];
+      let result = self.check(expr, &mut types)
+        .and_then(|_| self.eval(expr, &mut vec![self.globals.clone()], 0));
+      match result {
+        Ok(value) => println!("{:<28} -> {}: {}", text, value, value.ty()),
+        Err(e) => println!("{}\n{}", text, e),
+      }
+    }
+  }
+
+  fn run_stmt(&mut self, stmt: &Stmt) -> Result<String, Error> {
+    match stmt.kind {
+      StmtKind::Let(ref name, ty, ref expr) => {
+        let found = self.check(expr, &mut vec![self.global_types()])?;
+        if let Some(ty) = ty {
+          if ty != found {
+            return Err(Error::mismatch(ty, found));
+          }
+        }
+        let value = self.eval(expr, &mut vec![self.globals.clone()], 0)?;
+        self.globals.insert(name.clone(), value);
+        Ok(format!("statement, binds {}: {} = {}", name, found, value))
+      },
+      StmtKind::Fn(ref def) => {
+        // Like rustc, a name can only be defined once. Swapping in a new
+        // body would leave every fn that calls it checked against the old
+        // signature.
+        if let Some(old) = self.fns.get(&def.name) {
+          return Err(Error::compile("E0428", &format!("the name `{}` is defined multiple times", def.name))
+            .note(format!("note: previous definition was `{}`", signature(old))));
+        }
+        // Registered before checking the body, so it can call itself.
+        self.fns.insert(def.name.clone(), def.clone());
+        if let Err(e) = self.check_fn(def) {
+          self.fns.remove(&def.name);
+          return Err(e);
+        }
+        Ok(format!("statement, defines {}", signature(def)))
+      },
+      StmtKind::Expr(ref expr, semi) => {
+        let found = self.check(expr, &mut vec![self.global_types()])?;
+        if !semi && found != Ty::Unit {
+          return Err(Error::mismatch(Ty::Unit, found)
+            .note("help: consider using a semicolon here".to_string()));
+        }
+        let value = self.eval(expr, &mut vec![self.globals.clone()], 0)?;
+        if semi {
+          Ok(format!("-> (), the `;` threw away {}: {}", value, found))
+        } else {
+          Ok("-> ()".to_string())
+        }
+      },
+    }
+  }
+
+  fn global_types(&self) -> HashMap<String, Ty> {
+    self.globals.iter().map(|(name, value)| (name.clone(), value.ty())).collect()
+  }
+
+  // A fn body only sees its own params, not the locals around it, and
+  // has to evaluate to the declared return type.
+  fn check_fn(&self, def: &FnDef) -> Result<(), Error> {
+    let params = def.params.iter().cloned().collect();
+    let found = self.check_block(&def.body, &mut vec![params])?;
+    if found == def.ret {
+      return Ok(());
+    }
+
+    let mut err = Error::mismatch(def.ret, found);
+    if def.body.tail.is_none() {
+      err = err.note(format!(
+        "note: `{}` implicitly returns `()` as its body has no tail or `return` expression",
+        def.name));
+      if let Some(&Stmt { kind: StmtKind::Expr(ref last, true), .. }) = def.body.stmts.last() {
+        let mut params = vec![def.params.iter().cloned().collect()];
+        if self.check(last, &mut params).ok() == Some(def.ret) {
+          err = err.note("help: remove this semicolon to return this value".to_string());
+        }
+      }
+    }
+    Err(err)
+  }
+
+  fn check_block(&self, block: &Block, scopes: &mut Scopes<Ty>) -> Result<Ty, Error> {
+    scopes.push(HashMap::new());
+    let result = self.check_block_inner(block, scopes);
+    scopes.pop();
+    result
+  }
+
+  fn check_block_inner(&self, block: &Block, scopes: &mut Scopes<Ty>) -> Result<Ty, Error> {
+    for stmt in &block.stmts {
+      match stmt.kind {
+        StmtKind::Let(ref name, ty, ref expr) => {
+          let found = self.check(expr, scopes)?;
+          if let Some(ty) = ty {
+            if ty != found {
+              return Err(Error::mismatch(ty, found));
+            }
+          }
+          scopes.last_mut().expect("block scope").insert(name.clone(), found);
+        },
+        StmtKind::Expr(ref expr, semi) => {
+          let found = self.check(expr, scopes)?;
+          if !semi && found != Ty::Unit {
+            return Err(Error::mismatch(Ty::Unit, found)
+              .note("help: consider using a semicolon here".to_string()));
+          }
+        },
+        StmtKind::Fn(_) => {
+          return Err(Error::syntax("fn items are only supported at the top level here".to_string()));
+        },
+      }
+    }
+    match block.tail {
+      Some((ref expr, _, _)) => self.check(expr, scopes),
+      None => Ok(Ty::Unit),
+    }
+  }
+
+  fn check(&self, expr: &Expr, scopes: &mut Scopes<Ty>) -> Result<Ty, Error> {
+    match *expr {
+      Expr::Lit(value) => Ok(value.ty()),
+      Expr::Var(ref name) => match lookup(scopes, name) {
+        Some(&ty) => Ok(ty),
+        None if self.globals.contains_key(name) =>
+          Err(Error::compile("E0434", "can't capture dynamic environment in a fn item")
+            .note("help: use the `|| { ... }` closure form instead".to_string())),
+        None => Err(Error::compile("E0425", &format!("cannot find value `{}` in this scope", name))),
+      },
+      Expr::Call(ref name, ref args) => {
+        let def = match self.fns.get(name) {
+          Some(def) => def,
+          None => return Err(Error::compile("E0425", &format!("cannot find function `{}` in this scope", name))),
+        };
+        if def.params.len() != args.len() {
+          let plural = |n: usize| if n == 1 { "" } else { "s" };
+          return Err(Error::compile("E0061", &format!(
+            "this function takes {} argument{} but {} argument{} {} supplied",
+            def.params.len(), plural(def.params.len()), args.len(), plural(args.len()),
+            if args.len() == 1 { "was" } else { "were" })));
+        }
+        for (arg, &(_, ty)) in args.iter().zip(def.params.iter()) {
+          let found = self.check(arg, scopes)?;
+          if found != ty {
+            return Err(Error::mismatch(ty, found));
+          }
+        }
+        Ok(def.ret)
+      },
+      Expr::Neg(ref inner) => match self.check(inner, scopes)? {
+        Ty::I32 => Ok(Ty::I32),
+        ty => Err(Error::compile("E0600", &format!("cannot apply unary operator `-` to type `{}`", ty))),
+      },
+      Expr::Not(ref inner) => match self.check(inner, scopes)? {
+        Ty::Unit => Err(Error::compile("E0600", "cannot apply unary operator `!` to type `()`")),
+        ty => Ok(ty),
+      },
+      Expr::Binary(op, ref lhs, ref rhs) => {
+        let l = self.check(lhs, scopes)?;
+        let r = self.check(rhs, scopes)?;
+        match op {
+          BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Rem => {
+            if l != Ty::I32 {
+              return Err(Error::compile("E0369", &op.cannot(l, r)));
+            }
+            if r != Ty::I32 {
+              return Err(Error::mismatch(Ty::I32, r));
+            }
+            Ok(Ty::I32)
+          },
+          BinOp::And | BinOp::Or => {
+            if l != Ty::Bool {
+              return Err(Error::mismatch(Ty::Bool, l));
+            }
+            if r != Ty::Bool {
+              return Err(Error::mismatch(Ty::Bool, r));
+            }
+            Ok(Ty::Bool)
+          },
+          _ => {
+            if l != r {
+              return Err(Error::mismatch(l, r));
+            }
+            Ok(Ty::Bool)
+          },
+        }
+      },
+      Expr::Block(ref block) => self.check_block(block, scopes),
+      Expr::If(ref cond, ref then, ref otherwise) => {
+        // "the condition of an if *must* be a bool"
+        let found = self.check(cond, scopes)?;
+        if found != Ty::Bool {
+          return Err(Error::mismatch(Ty::Bool, found));
+        }
+        let then_ty = self.check_block(then, scopes)?;
+        match *otherwise {
+          Some(ref otherwise) => {
+            let else_ty = self.check(otherwise, scopes)?;
+            if then_ty != else_ty {
+              return Err(Error::compile("E0308", "`if` and `else` have incompatible types")
+                .note(format!("note: expected `{}`, found `{}`", then_ty, else_ty)));
+            }
+            Ok(then_ty)
+          },
+          None if then_ty != Ty::Unit =>
+            Err(Error::compile("E0317", "`if` may be missing an `else` clause")
+              .note(format!("note: expected `()`, found `{}`", then_ty))),
+          None => Ok(Ty::Unit),
+        }
+      },
+    }
+  }
+
+  fn eval_block(&self, block: &Block, scopes: &mut Scopes<Value>, depth: usize) -> Result<Value, Error> {
+    scopes.push(HashMap::new());
+    let result = self.eval_block_inner(block, scopes, depth);
+    scopes.pop();
+    result
+  }
+
+  fn eval_block_inner(&self, block: &Block, scopes: &mut Scopes<Value>, depth: usize) -> Result<Value, Error> {
+    for stmt in &block.stmts {
+      match stmt.kind {
+        StmtKind::Let(ref name, _, ref expr) => {
+          let value = self.eval(expr, scopes, depth)?;
+          scopes.last_mut().expect("block scope").insert(name.clone(), value);
+        },
+        StmtKind::Expr(ref expr, _) => {
+          self.eval(expr, scopes, depth)?;
+        },
+        StmtKind::Fn(_) => unreachable!("rejected by check"),
+      }
+    }
+    match block.tail {
+      Some((ref expr, _, _)) => self.eval(expr, scopes, depth),
+      None => Ok(Value::Unit),
+    }
+  }
+
+  // Everything has been type checked by now, so the only things left to
+  // go wrong are the ones rustc would leave to a runtime panic.
+  fn eval(&self, expr: &Expr, scopes: &mut Scopes<Value>, depth: usize) -> Result<Value, Error> {
+    let int = |value: Value| match value {
+      Value::I32(n) => n,
+      _ => unreachable!("checked to be i32"),
+    };
+    let boolean = |value: Value| match value {
+      Value::Bool(b) => b,
+      _ => unreachable!("checked to be bool"),
+    };
+
+    match *expr {
+      Expr::Lit(value) => Ok(value),
+      Expr::Var(ref name) => Ok(*lookup(scopes, name).expect("checked to exist")),
+      Expr::Call(ref name, ref args) => {
+        if depth == MAX_DEPTH {
+          return Err(Error::panic("recursed too deep, would have overflowed the stack"));
+        }
+        let def = &self.fns[name];
+        let mut frame = HashMap::new();
+        for (arg, (param, _)) in args.iter().zip(def.params.iter()) {
+          frame.insert(param.clone(), self.eval(arg, scopes, depth)?);
+        }
+        self.eval_block(&def.body, &mut vec![frame], depth + 1)
+      },
+      Expr::Neg(ref inner) => {
+        let n = int(self.eval(inner, scopes, depth)?);
+        n.checked_neg().map(Value::I32).ok_or_else(|| Error::panic("attempt to negate with overflow"))
+      },
+      Expr::Not(ref inner) => match self.eval(inner, scopes, depth)? {
+        Value::I32(n) => Ok(Value::I32(!n)),
+        Value::Bool(b) => Ok(Value::Bool(!b)),
+        Value::Unit => unreachable!("checked not to be ()"),
+      },
+      Expr::Binary(BinOp::And, ref lhs, ref rhs) => {
+        let l = boolean(self.eval(lhs, scopes, depth)?);
+        Ok(Value::Bool(l && boolean(self.eval(rhs, scopes, depth)?)))
+      },
+      Expr::Binary(BinOp::Or, ref lhs, ref rhs) => {
+        let l = boolean(self.eval(lhs, scopes, depth)?);
+        Ok(Value::Bool(l || boolean(self.eval(rhs, scopes, depth)?)))
+      },
+      Expr::Binary(op, ref lhs, ref rhs) => {
+        let l = self.eval(lhs, scopes, depth)?;
+        let r = self.eval(rhs, scopes, depth)?;
+        let result = match op {
+          BinOp::Add => int(l).checked_add(int(r)),
+          BinOp::Sub => int(l).checked_sub(int(r)),
+          BinOp::Mul => int(l).checked_mul(int(r)),
+          BinOp::Div if int(r) == 0 => return Err(Error::panic("attempt to divide by zero")),
+          BinOp::Rem if int(r) == 0 =>
+            return Err(Error::panic("attempt to calculate the remainder with a divisor of zero")),
+          BinOp::Div => int(l).checked_div(int(r)),
+          BinOp::Rem => int(l).checked_rem(int(r)),
+          BinOp::Eq => return Ok(Value::Bool(l == r)),
+          BinOp::Ne => return Ok(Value::Bool(l != r)),
+          _ => {
+            let ord = match (l, r) {
+              (Value::I32(a), Value::I32(b)) => a.cmp(&b),
+              (Value::Bool(a), Value::Bool(b)) => a.cmp(&b),
+              _ => ::std::cmp::Ordering::Equal,
+            };
+            let result = match op {
+              BinOp::Lt => ord.is_lt(),
+              BinOp::Gt => ord.is_gt(),
+              BinOp::Le => ord.is_le(),
+              _ => ord.is_ge(),
+            };
+            return Ok(Value::Bool(result));
+          },
+        };
+        result.map(Value::I32)
+          .ok_or_else(|| Error::panic(&format!("attempt to {} with overflow", op.verb())))
+      },
+      Expr::Block(ref block) => self.eval_block(block, scopes, depth),
+      Expr::If(ref cond, ref then, ref otherwise) => {
+        if boolean(self.eval(cond, scopes, depth)?) {
+          self.eval_block(then, scopes, depth)
+        } else {
+          match *otherwise {
+            Some(ref otherwise) => self.eval(otherwise, scopes, depth),
+            None => Ok(Value::Unit),
+          }
+        }
+      },
+    }
+  }
+}
+
+fn signature(def: &FnDef) -> String {
+  let params: Vec<String> = def.params.iter()
+    .map(|&(ref name, ty)| format!("{}: {}", name, ty))
+    .collect();
+  format!("{}({}) -> {}", def.name, params.join(", "), def.ret)
+}
@@ -0,0 +1,160 @@
+////////////////////////////////////////////////////////////////////////////////
+// Exercise: generate the nth Fibonacci number
+////////////////////////////////////////////////////////////////////////////////
+// F(0) = 0, F(1) = 1, F(n) = F(n - 1) + F(n - 2)
+//
+// Four ways of getting there, all in u128 since the numbers get big fast.
+// F(93) is already too big for a u64. Every addition and multiplication
+// is *checked*: checked_add and friends give back None instead of
+// silently wrapping (release builds) or panicking (debug builds) when the
+// result doesn't fit, so each strategy returns None once n is too big.
+//
+// + iterative: a loop carrying the last two numbers. n additions.
+// + naive: the definition as written, calling itself twice per call.
+//   The calls fan out into a tree with roughly F(n) leaves, so n = 40
+//   makes hundreds of millions of calls.
+// + memoised: the same recursion, but each answer is saved the first time
+//   it's worked out, so every F(k) is only computed once.
+// + fast doubling: jumps from F(k) and F(k + 1) straight to F(2k) and
+//   F(2k + 1), so it only needs about log2(n) steps.
+//     F(2k)     = F(k) * (2 * F(k + 1) - F(k))
+//     F(2k + 1) = F(k)^2 + F(k + 1)^2
+////////////////////////////////////////////////////////////////////////////////
+
+pub fn iterative(n: u32) -> Option<u128> {
+  if n == 0 {
+    return Some(0);
+  }
+  // Stop one step short of F(n + 1), which can overflow when F(n) fits.
+  let (mut a, mut b): (u128, u128) = (0, 1);
+  for _ in 1..n {
+    let next = a.checked_add(b)?;
+    a = b;
+    b = next;
+  }
+  Some(b)
+}
+
+// calls counts how many times naive was called, to show the blow up.
+pub fn naive(n: u32, calls: &mut u64) -> Option<u128> {
+  *calls += 1;
+  if n < 2 {
+    return Some(n as u128);
+  }
+  naive(n - 1, calls)?.checked_add(naive(n - 2, calls)?)
+}
+
+// F(186) is the last one that fits in a u128, see largest_per_type().
+pub const MAX_N: u32 = 186;
+
+pub fn memoised(n: u32) -> Option<u128> {
+  fn go(n: u32, memo: &mut Vec<Option<u128>>) -> Option<u128> {
+    if let Some(known) = memo[n as usize] {
+      return Some(known);
+    }
+    let value = go(n - 1, memo)?.checked_add(go(n - 2, memo)?)?;
+    memo[n as usize] = Some(value);
+    Some(value)
+  }
+
+  // Every level of recursion is a stack frame, so don't go a million deep
+  // for an answer that can't fit anyway.
+  if n > MAX_N {
+    return None;
+  }
+  let mut memo = vec![None; n as usize + 2];
+  memo[0] = Some(0);
+  memo[1] = Some(1);
+  go(n, &mut memo)
+}
+
+pub fn fast_doubling(n: u32) -> Option<u128> {
+  // Gives (F(n), F(n + 1)).
+  fn pair(n: u32) -> Option<(u128, u128)> {
+    if n == 0 {
+      return Some((0, 1));
+    }
+    let (a, b) = pair(n / 2)?;
+    let even = a.checked_mul(b.checked_mul(2)?.checked_sub(a)?)?;
+    let odd = a.checked_mul(a)?.checked_add(b.checked_mul(b)?)?;
+    if n.is_multiple_of(2) {
+      Some((even, odd))
+    } else {
+      Some((odd, even.checked_add(odd)?))
+    }
+  }
+
+  // Only work out the half we need at the top, F(n + 1) can overflow
+  // when F(n) doesn't.
+  if n == 0 {
+    return Some(0);
+  }
+  let (a, b) = pair(n / 2)?;
+  if n.is_multiple_of(2) {
+    a.checked_mul(b.checked_mul(2)?.checked_sub(a)?)
+  } else {
+    a.checked_mul(a)?.checked_add(b.checked_mul(b)?)
+  }
+}
+
+// For each integer type: the largest n whose F(n) fits, and that F(n).
+// Written once as a macro since the loop is the same for every type,
+// it's only the type that changes.
+macro_rules! largest {
+  ($($t:ty),*) => {
+    vec![$({
+      let (mut n, mut a, mut b): (u32, $t, $t) = (0, 0, 1);
+      // Keep stepping while the *next* number still fits.
+      while let Some(next) = a.checked_add(b) {
+        a = b;
+        b = next;
+        n += 1;
+      }
+      // a is F(n), b is F(n + 1), and F(n + 2) didn't fit.
+      (stringify!($t), n + 1, b.to_string())
+    }),*]
+  };
+}
+
+pub fn largest_per_type() -> Vec<(&'static str, u32, String)> {
+  largest!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn first_few() {
+    let expected = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+    for (n, &f) in expected.iter().enumerate() {
+      assert_eq!(iterative(n as u32), Some(f), "F({})", n);
+    }
+  }
+
+  #[test]
+  fn all_four_agree() {
+    for n in 0..=25 {
+      let mut calls = 0;
+      assert_eq!(naive(n, &mut calls), iterative(n), "naive F({})", n);
+      // One call per node of the tree, which is what the lesson quotes
+      // when it skips naive.
+      assert_eq!(calls as u128, 2 * iterative(n + 1).unwrap() - 1, "calls for F({})", n);
+    }
+    for n in (0..=MAX_N + 1).chain(vec![MAX_N + 2, 1_000, u32::MAX]) {
+      let answer = iterative(n);
+      assert_eq!(memoised(n), answer, "memoised F({})", n);
+      assert_eq!(fast_doubling(n), answer, "fast doubling F({})", n);
+    }
+  }
+
+  #[test]
+  fn max_n_is_the_last_that_fits() {
+    assert_eq!(iterative(MAX_N), Some(332_825_110_087_067_562_321_196_029_789_634_457_848));
+    assert_eq!(iterative(MAX_N + 1), None);
+    assert_eq!(memoised(MAX_N + 1), None);
+    assert_eq!(fast_doubling(MAX_N + 1), None);
+    let (ty, n, _) = largest_per_type().pop().unwrap();
+    assert_eq!((ty, n), ("u128", MAX_N));
+  }
+}
@@ -0,0 +1,491 @@
+////////////////////////////////////////////////////////////////////////////////
+// Numeric literals
+////////////////////////////////////////////////////////////////////////////////
+// The integer section of the data types notes never covers how you can
+// actually *write* numbers. Rust accepts a handful of literal forms:
+//
+//   Decimal      98_222      (underscores are just visual separators)
+//   Hex          0xff
+//   Octal        0o77
+//   Binary       0b1111_0000
+//   Byte (u8)    b'A'
+//
+// Any number literal except the byte literal can carry a type suffix,
+// like 57u8 or 1_000u64 or 2.5e-3f32. Without a suffix, integers default
+// to i32 and floats default to f64, which lines up with "i32 is usually
+// fine" and "default fpoint is f64" from the data types notes.
+//
+// parse() takes the text of a literal and works out what rustc would: the
+// type it ends up with and the value it holds. Bad literals get an error
+// that reads roughly like the one rustc would print for the same text.
+////////////////////////////////////////////////////////////////////////////////
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ty {
+  I8, I16, I32, I64, I128, Isize,
+  U8, U16, U32, U64, U128, Usize,
+  F32, F64,
+}
+
+impl Ty {
+  fn from_suffix(suffix: &str) -> Option<Ty> {
+    let ty = match suffix {
+      "i8" => Ty::I8, "i16" => Ty::I16, "i32" => Ty::I32,
+      "i64" => Ty::I64, "i128" => Ty::I128, "isize" => Ty::Isize,
+      "u8" => Ty::U8, "u16" => Ty::U16, "u32" => Ty::U32,
+      "u64" => Ty::U64, "u128" => Ty::U128, "usize" => Ty::Usize,
+      "f32" => Ty::F32, "f64" => Ty::F64,
+      _ => return None,
+    };
+    Some(ty)
+  }
+
+  pub fn name(&self) -> &'static str {
+    match *self {
+      Ty::I8 => "i8", Ty::I16 => "i16", Ty::I32 => "i32",
+      Ty::I64 => "i64", Ty::I128 => "i128", Ty::Isize => "isize",
+      Ty::U8 => "u8", Ty::U16 => "u16", Ty::U32 => "u32",
+      Ty::U64 => "u64", Ty::U128 => "u128", Ty::Usize => "usize",
+      Ty::F32 => "f32", Ty::F64 => "f64",
+    }
+  }
+
+  // isize and usize are as wide as a pointer on the machine we're on.
+  pub fn bits(&self) -> u32 {
+    match *self {
+      Ty::I8 | Ty::U8 => 8,
+      Ty::I16 | Ty::U16 => 16,
+      Ty::I32 | Ty::U32 | Ty::F32 => 32,
+      Ty::I64 | Ty::U64 | Ty::F64 => 64,
+      Ty::I128 | Ty::U128 => 128,
+      Ty::Isize | Ty::Usize => usize::BITS,
+    }
+  }
+
+  pub fn is_float(&self) -> bool {
+    *self == Ty::F32 || *self == Ty::F64
+  }
+
+  pub fn is_signed(&self) -> bool {
+    matches!(*self, Ty::I8 | Ty::I16 | Ty::I32 | Ty::I64 | Ty::I128 | Ty::Isize)
+  }
+
+  // Largest value an integer type can hold, as a u128 so every type fits.
+  fn max(&self) -> u128 {
+    let bits = if self.is_signed() { self.bits() - 1 } else { self.bits() };
+    if bits == 128 { u128::MAX } else { (1u128 << bits) - 1 }
+  }
+
+  fn range(&self) -> String {
+    if self.is_signed() {
+      format!("-{}..={}", self.max() + 1, self.max())
+    } else {
+      format!("0..={}", self.max())
+    }
+  }
+}
+
+// Where the type of a literal came from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Origin {
+  Suffix,
+  Default,
+  Byte,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+  Int(u128),
+  F32(f32),
+  F64(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Literal {
+  pub src: String,
+  pub ty: Ty,
+  pub origin: Origin,
+  pub value: Value,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LitError {
+  NotALiteral(String),
+  NoDigits,
+  InvalidDigit(u32),
+  EmptyExponent,
+  UnsupportedFloatBase(u32),
+  InvalidIntSuffix(String),
+  InvalidFloatSuffix(String),
+  TooLarge,
+  OutOfRange { src: String, ty: Ty, value: u128, radix: u32 },
+  FloatOutOfRange(Ty),
+  EmptyByte,
+  NonAsciiByte(char),
+  UnknownEscape(char),
+  EscapeTooShort,
+  InvalidEscapeChar(char),
+  UnescapedQuote,
+  UnicodeEscapeInByte,
+  ByteTooLong(String),
+  UnterminatedByte,
+}
+
+// The first line mirrors rustc's own error, any `= note`/`= help` lines
+// underneath mirror the extra context it prints.
+impl fmt::Display for LitError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      LitError::NotALiteral(ref src) =>
+        write!(f, "error: expected a numeric literal, found `{}`", src),
+      LitError::NoDigits =>
+        write!(f, "error: no valid digits found for number"),
+      LitError::InvalidDigit(radix) =>
+        write!(f, "error: invalid digit for a base {} literal", radix),
+      LitError::EmptyExponent =>
+        write!(f, "error: expected at least one digit in exponent"),
+      LitError::UnsupportedFloatBase(radix) => {
+        let base = match radix { 2 => "binary", 8 => "octal", _ => "hexadecimal" };
+        write!(f, "error: {} float literal is not supported", base)
+      },
+      LitError::InvalidIntSuffix(ref suffix) => write!(f,
+        "error: invalid suffix `{}` for number literal\n  \
+         = help: the suffix must be one of the numeric types (`u32`, `isize`, `f32`, etc.)",
+        suffix),
+      LitError::InvalidFloatSuffix(ref suffix) => write!(f,
+        "error: invalid suffix `{}` for float literal\n  \
+         = help: valid suffixes are `f32` and `f64`",
+        suffix),
+      LitError::TooLarge =>
+        write!(f, "error: integer literal is too large"),
+      LitError::OutOfRange { ref src, ty, value, radix } => {
+        writeln!(f, "error: literal out of range for `{}`", ty.name())?;
+        // A non-decimal literal that fits the unsigned type of the same
+        // width is just a bit pattern, so rustc says what it wraps to.
+        if radix != 10 && ty.is_signed() && value <= (ty.max() << 1 | 1) {
+          let shift = 128 - ty.bits();
+          let wrapped = ((value << shift) as i128) >> shift;
+          write!(f,
+            "  = note: the literal `{}` (decimal `{}`) does not fit into the \
+             type `{}` and will become `{}{}`",
+            src, value, ty.name(), wrapped, ty.name())
+        } else {
+          write!(f,
+            "  = note: the literal `{}` does not fit into the type `{}` \
+             whose range is `{}`",
+            src, ty.name(), ty.range())
+        }
+      },
+      LitError::FloatOutOfRange(ty) => write!(f,
+        "error: literal out of range for `{}`\n  \
+         = note: the literal would be converted to `{}::INFINITY`",
+        ty.name(), ty.name()),
+      LitError::EmptyByte =>
+        write!(f, "error: empty character literal"),
+      LitError::NonAsciiByte(c) => write!(f,
+        "error: non-ASCII character in byte literal\n  \
+         = help: if you meant to use the unicode code point for {:?}, use a \\xHH escape",
+        c),
+      LitError::UnknownEscape(c) =>
+        write!(f, "error: unknown byte escape: `{}`", c),
+      LitError::EscapeTooShort =>
+        write!(f, "error: numeric character escape is too short"),
+      LitError::InvalidEscapeChar(c) =>
+        write!(f, "error: invalid character in numeric character escape: `{}`", c),
+      LitError::UnescapedQuote => write!(f,
+        "error: byte constant must be escaped: `'`\n  \
+         = help: escape the character: `b'\\''`"),
+      LitError::UnicodeEscapeInByte => write!(f,
+        "error: unicode escape in byte string\n  \
+         = help: unicode escape sequences cannot be used as a byte or in a byte string"),
+      LitError::ByteTooLong(ref contents) => write!(f,
+        "error: character literal may only contain one codepoint\n  \
+         = help: if you meant to write a byte string literal, use double quotes: `b\"{}\"`",
+        contents),
+      LitError::UnterminatedByte =>
+        write!(f, "error: unterminated byte constant"),
+    }
+  }
+}
+
+pub fn parse(src: &str) -> Result<Literal, LitError> {
+  let src = src.trim();
+  if src.starts_with("b'") {
+    return parse_byte(src);
+  }
+
+  if !src.starts_with(|c: char| c.is_ascii_digit()) {
+    return Err(LitError::NotALiteral(src.to_string()));
+  }
+
+  let (radix, body) = match src.get(..2) {
+    Some("0x") => (16, &src[2..]),
+    Some("0o") => (8, &src[2..]),
+    Some("0b") => (2, &src[2..]),
+    _ => (10, src),
+  };
+
+  // Hex literals can't have float suffixes: the f in 0x1f32 is a digit.
+  let is_digit = |c: char| c == '_' || c.is_digit(if radix == 16 { 16 } else { 10 });
+  let int_end = body.find(|c: char| !is_digit(c)).unwrap_or(body.len());
+  let (digits, mut rest) = body.split_at(int_end);
+
+  // 0b102 lexes as one literal, the 2 just isn't a binary digit.
+  if digits.chars().any(|c| c != '_' && !c.is_digit(radix)) {
+    return Err(LitError::InvalidDigit(radix));
+  }
+  if !digits.chars().any(|c| c != '_') {
+    return Err(LitError::NoDigits);
+  }
+
+  // Decimal literals may keep going with a fraction and/or an exponent.
+  let mut is_float = false;
+  let mut float_len = int_end;
+  if rest.starts_with('.') && !rest[1..].starts_with(|c: char| c == '.' || c == '_' || c.is_alphabetic()) {
+    is_float = true;
+    let frac = &rest[1..];
+    let frac_len = frac.find(|c: char| !(c == '_' || c.is_ascii_digit())).unwrap_or(frac.len());
+    float_len += 1 + frac_len;
+    rest = &frac[frac_len..];
+  }
+  if rest.starts_with('e') || rest.starts_with('E') {
+    is_float = true;
+    let mut exp = &rest[1..];
+    let mut exp_len = 1;
+    if exp.starts_with('+') || exp.starts_with('-') {
+      exp = &exp[1..];
+      exp_len += 1;
+    }
+    let digits_len = exp.find(|c: char| !(c == '_' || c.is_ascii_digit())).unwrap_or(exp.len());
+    if !exp[..digits_len].chars().any(|c| c.is_ascii_digit()) {
+      return Err(LitError::EmptyExponent);
+    }
+    float_len += exp_len + digits_len;
+    rest = &exp[digits_len..];
+  }
+
+  if is_float && radix != 10 {
+    return Err(LitError::UnsupportedFloatBase(radix));
+  }
+
+  let suffix = rest;
+  let ty = if suffix.is_empty() {
+    None
+  } else {
+    match Ty::from_suffix(suffix) {
+      Some(ty) if ty.is_float() && radix != 10 =>
+        return Err(LitError::UnsupportedFloatBase(radix)),
+      Some(ty) if is_float && !ty.is_float() =>
+        return Err(LitError::InvalidFloatSuffix(suffix.to_string())),
+      Some(ty) => Some(ty),
+      None if is_float =>
+        return Err(LitError::InvalidFloatSuffix(suffix.to_string())),
+      None => return Err(LitError::InvalidIntSuffix(suffix.to_string())),
+    }
+  };
+
+  // 1f32 is a float even though it's written like an integer.
+  if is_float || ty.is_some_and(|t| t.is_float()) {
+    let text: String = body[..float_len].chars().filter(|c| *c != '_').collect();
+    return parse_float(src, &text, ty);
+  }
+
+  let mut value: u128 = 0;
+  for d in digits.chars().filter_map(|c| c.to_digit(radix)) {
+    value = value.checked_mul(radix as u128)
+      .and_then(|v| v.checked_add(d as u128))
+      .ok_or(LitError::TooLarge)?;
+  }
+
+  let (ty, origin) = match ty {
+    Some(ty) => (ty, Origin::Suffix),
+    None => (Ty::I32, Origin::Default),
+  };
+  if value > ty.max() {
+    return Err(LitError::OutOfRange { src: src.to_string(), ty, value, radix });
+  }
+
+  Ok(Literal { src: src.to_string(), ty, origin, value: Value::Int(value) })
+}
+
+fn parse_float(src: &str, text: &str, ty: Option<Ty>) -> Result<Literal, LitError> {
+  let (ty, origin) = match ty {
+    Some(ty) => (ty, Origin::Suffix),
+    None => (Ty::F64, Origin::Default),
+  };
+  // The lexer has already checked the shape, so these parses can't fail.
+  let value = if ty == Ty::F32 {
+    Value::F32(text.parse().expect("lexed float"))
+  } else {
+    Value::F64(text.parse().expect("lexed float"))
+  };
+  let infinite = match value {
+    Value::F32(v) => v.is_infinite(),
+    Value::F64(v) => v.is_infinite(),
+    Value::Int(_) => false,
+  };
+  if infinite {
+    return Err(LitError::FloatOutOfRange(ty));
+  }
+  Ok(Literal { src: src.to_string(), ty, origin, value })
+}
+
+fn parse_byte(src: &str) -> Result<Literal, LitError> {
+  let inner = &src[2..];
+  if inner.is_empty() {
+    return Err(LitError::UnterminatedByte);
+  }
+  if inner == "'" {
+    return Err(LitError::EmptyByte);
+  }
+
+  let mut chars = inner.chars();
+  let byte = match chars.next() {
+    Some('\\') => {
+      let esc = chars.next().ok_or(LitError::UnterminatedByte)?;
+      match esc {
+        'n' => b'\n',
+        'r' => b'\r',
+        't' => b'\t',
+        '\\' => b'\\',
+        '0' => b'\0',
+        '\'' => b'\'',
+        '"' => b'"',
+        'x' => {
+          // Exactly two hex digits, without running into the closing quote.
+          let mut byte = 0;
+          for _ in 0..2 {
+            match chars.clone().next() {
+              None | Some('\'') => return Err(LitError::EscapeTooShort),
+              Some(c) => {
+                let digit = c.to_digit(16).ok_or(LitError::InvalidEscapeChar(c))?;
+                byte = byte * 16 + digit as u8;
+                chars.next();
+              },
+            }
+          }
+          byte
+        },
+        'u' => return Err(LitError::UnicodeEscapeInByte),
+        other => return Err(LitError::UnknownEscape(other)),
+      }
+    },
+    // b''' , the middle quote would have to be b'\''.
+    Some('\'') => return Err(LitError::UnescapedQuote),
+    Some(c) if c.is_ascii() => c as u8,
+    Some(c) => return Err(LitError::NonAsciiByte(c)),
+    None => return Err(LitError::UnterminatedByte),
+  };
+
+  match chars.as_str() {
+    "'" => {},
+    // More than one char before the closing quote.
+    rest if rest.len() > 1 && rest.ends_with('\'') =>
+      return Err(LitError::ByteTooLong(inner[..inner.len() - 1].to_string())),
+    _ => return Err(LitError::UnterminatedByte),
+  }
+
+  Ok(Literal { src: src.to_string(), ty: Ty::U8, origin: Origin::Byte, value: Value::Int(byte as u128) })
+}
+
+// Splits a bit string into bytes so it's readable: 00000000 11110000
+fn group_bits(bits: &str) -> String {
+  bits.as_bytes()
+    .chunks(8)
+    .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+pub fn report(lit: &Literal) {
+  let origin = match lit.origin {
+    Origin::Suffix => format!("from the `{}` suffix", lit.ty.name()),
+    Origin::Default if lit.ty.is_float() => "default for an unsuffixed float".to_string(),
+    Origin::Default => "default for an unsuffixed integer".to_string(),
+    Origin::Byte => "byte literals are always u8".to_string(),
+  };
+  println!("{}", lit.src);
+  println!("  type    -> {} ({})", lit.ty.name(), origin);
+
+  let width = lit.ty.bits() as usize;
+  match lit.value {
+    Value::Int(v) => {
+      println!("  dec     -> {}", v);
+      println!("  hex     -> {:#x}", v);
+      println!("  oct     -> {:#o}", v);
+      println!("  bin     -> {:#b}", v);
+      println!("  bits    -> {}", group_bits(&format!("{:0w$b}", v, w = width)));
+    },
+    // Floats don't have a hex or binary *value* the way integers do, so
+    // show the IEEE 754 pieces they're stored as instead.
+    Value::F32(v) => {
+      let bits = v.to_bits();
+      println!("  value   -> {:e} ({})", v, v);
+      println!("  hex     -> {:#010x}", bits);
+      println!("  bits    -> {}", group_bits(&format!("{:032b}", bits)));
+      println!("  fields  -> sign {} | exponent {:08b} | mantissa {:023b}",
+        bits >> 31, (bits >> 23) & 0xff, bits & 0x7f_ffff);
+    },
+    Value::F64(v) => {
+      let bits = v.to_bits();
+      println!("  value   -> {:e} ({})", v, v);
+      println!("  hex     -> {:#018x}", bits);
+      println!("  bits    -> {}", group_bits(&format!("{:064b}", bits)));
+      println!("  fields  -> sign {} | exponent {:011b} | mantissa {:052b}",
+        bits >> 63, (bits >> 52) & 0x7ff, bits & 0xf_ffff_ffff_ffff);
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ok(src: &str, ty: Ty, origin: Origin, value: Value) -> Result<Literal, LitError> {
+    Ok(Literal { src: src.to_string(), ty, origin, value })
+  }
+
+  #[test]
+  fn table() {
+    let cases = [
+      ("98_222", ok("98_222", Ty::I32, Origin::Default, Value::Int(98_222))),
+      ("0xff", ok("0xff", Ty::I32, Origin::Default, Value::Int(255))),
+      ("0o77", ok("0o77", Ty::I32, Origin::Default, Value::Int(63))),
+      ("0b1111_0000", ok("0b1111_0000", Ty::I32, Origin::Default, Value::Int(240))),
+      ("1_000u64", ok("1_000u64", Ty::U64, Origin::Suffix, Value::Int(1000))),
+      ("2.5e-3f32", ok("2.5e-3f32", Ty::F32, Origin::Suffix, Value::F32(2.5e-3))),
+      ("1e10", ok("1e10", Ty::F64, Origin::Default, Value::F64(1e10))),
+      ("b'A'", ok("b'A'", Ty::U8, Origin::Byte, Value::Int(65))),
+      ("b'\\n'", ok("b'\\n'", Ty::U8, Origin::Byte, Value::Int(10))),
+      ("b'\\''", ok("b'\\''", Ty::U8, Origin::Byte, Value::Int(39))),
+      ("b'\\x7f'", ok("b'\\x7f'", Ty::U8, Origin::Byte, Value::Int(127))),
+      ("b'\\xFF'", ok("b'\\xFF'", Ty::U8, Origin::Byte, Value::Int(255))),
+      ("256u8", Err(LitError::OutOfRange { src: "256u8".to_string(), ty: Ty::U8, value: 256, radix: 10 })),
+      ("0b102", Err(LitError::InvalidDigit(2))),
+      ("7u7", Err(LitError::InvalidIntSuffix("u7".to_string()))),
+      ("0x1.0", Err(LitError::UnsupportedFloatBase(16))),
+      ("b''", Err(LitError::EmptyByte)),
+      ("b'a", Err(LitError::UnterminatedByte)),
+      ("b'''", Err(LitError::UnescapedQuote)),
+      ("b'ab'", Err(LitError::ByteTooLong("ab".to_string()))),
+      ("b'é'", Err(LitError::NonAsciiByte('é'))),
+      ("b'\\q'", Err(LitError::UnknownEscape('q'))),
+      ("b'\\u{41}'", Err(LitError::UnicodeEscapeInByte)),
+      ("b'\\x4'", Err(LitError::EscapeTooShort)),
+      ("b'\\x'", Err(LitError::EscapeTooShort)),
+      ("b'\\xZZ'", Err(LitError::InvalidEscapeChar('Z'))),
+    ];
+    for &(src, ref expected) in cases.iter() {
+      assert_eq!(&parse(src), expected, "{}", src);
+    }
+  }
+
+  #[test]
+  fn byte_errors_read_like_rustc() {
+    let first_line = |src: &str| parse(src).unwrap_err().to_string().lines().next().unwrap().to_string();
+    assert_eq!(first_line("b'''"), "error: byte constant must be escaped: `'`");
+    assert_eq!(first_line("b'\\x4'"), "error: numeric character escape is too short");
+    assert_eq!(first_line("b'\\q'"), "error: unknown byte escape: `q`");
+  }
+}
@@ -1,6 +1,16 @@
+mod calendar;
+mod countdown;
+mod eval;
+mod fibonacci;
+mod literals;
+mod temperature;
+mod twelve_days;
+
 fn main() {
+  //shadowing()
   //data_types()
   //func()
+  //exercises()
   ctl_flow();
 }
 /* Shadowing is different than marking a var as mut, because unless
@@ -17,7 +27,53 @@ fn main() {
  * let spaces = spaces.len();
  *
  * lets us use spaces for both instead of spaces_str and spaces_num.
+ *
+ * See shadowing()
  */
+fn shadowing() {
+  let spaces = "    ";
+  let spaces = spaces.len();
+  println!("spaces -> {}", spaces);
+
+  // With mut we'd only be allowed to change the value, never the type.
+  // This won't compile:
+  //
+  // let mut spaces = "    ";
+  // spaces = spaces.len();
+  //
+  // error[E0308]: mismatched types
+  //   |
+  //   |   let mut spaces = "    ";
+  //   |                    ------ expected due to this value
+  //   |   spaces = spaces.len();
+  //   |            ^^^^^^^^^^^^ expected `&str`, found `usize`
+
+  // A shadow only lasts as long as the scope it was made in. Once the
+  // inner block closes, x goes back to meaning the outer x.
+  let x = 5;
+  let x = x + 1;
+  {
+    let x = x * 2;
+    println!("x in the inner block -> {}", x);
+  }
+  println!("x after the inner block -> {}", x);
+
+  // Shadowing doesn't drop the old value either. It's still alive, we've
+  // just lost the name for it, so it gets dropped at the end of the scope
+  // along with everything else (newest first), not at the second `let`.
+  struct Loud(String);
+  impl Drop for Loud {
+    fn drop(&mut self) {
+      println!("dropping {}", self.0);
+    }
+  }
+
+  let s = Loud(String::from("first s"));
+  println!("made {}", s.0);
+  let s = Loud(String::from("second s"));
+  println!("made {}, first s is shadowed but not dropped", s.0);
+  println!("end of shadowing()");
+}
 
 ////////////////////////////////////////////////////////////////////////////////
 // Data types
@@ -33,12 +89,23 @@ fn main() {
 //
 // let guess: u32 = "42".parse().expect("Not a number!");
 //
+// The annotation is what tells parse which type to produce; without it
+// rustc has no way to know. See parse_play() to try it on any input.
+//
+// To see what got inferred, run any of the demos with SHOW_TYPES set,
+// i.e. SHOW_TYPES=1 cargo run, and they'll print the type of each of
+// their bindings. See show_type()
+//
 // [Scalar Types]
 // These types represent a single value. There are four primary ones,
 // integers, floating-point nums, booleans, and characters.
 // Integers: primary is usually fine, i32. isize/usize are primarily
 // used indexing some sort of collection
 //
+// Integer literals can be written in decimal, hex (0xff), octal (0o77),
+// binary (0b1111_0000) or as a byte (b'A'), with an optional type suffix
+// like 1_000u64. See literals()
+//
 // Default fpoint is f64 because it's roughly the same speed as f32 and
 // offers more precision.
 //
@@ -66,17 +133,241 @@ fn main() {
 fn data_types() {
   println!("Hello data types.");
   //tup();
+  //literals();
+  //parse_play();
+  //months();
+  //arr_vs_vec();
   arr();
 }
 
+fn literals() {
+  // Pass literals on the command line to inspect them, i.e.
+  // cargo run -- 0xff 2.5e-3f32 "b'A'"
+  // otherwise run through a few of each kind, including some bad ones.
+  let mut srcs: Vec<String> = std::env::args().skip(1).collect();
+  if srcs.is_empty() {
+    srcs = ["98_222", "0xff", "0o77", "0b1111_0000", "b'A'", "1_000u64",
+      "2.5e-3f32", "1e10", "0xffi8", "256u8", "0b102", "7u7", "0x1.0",
+      "b'ab'", "b'\\u{41}'"]
+      .iter().map(|s| s.to_string()).collect();
+  }
+
+  for src in &srcs {
+    match literals::parse(src) {
+      Ok(lit) => literals::report(&lit),
+      Err(e) => println!("{}\n{}", src, e),
+    }
+    println!();
+  }
+}
+
+// Prints the type rustc settled on for a binding when SHOW_TYPES is set.
+// Nothing here is annotated, so it's all inference: integer literals
+// with nothing else to go on fall back to i32, floats to f64.
+fn show_type<T>(name: &str, _: &T) {
+  if std::env::var_os("SHOW_TYPES").is_some() {
+    println!("{}: {}", name, std::any::type_name::<T>());
+  }
+}
+
+fn parse_play() {
+  // Scriptable:  cargo run -- u8 255 256 -1
+  //              cargo run -- --expect u32 42x
+  // Interactive: cargo run, then lines like `f64 2.5e3` (or pipe them in)
+  let mut args: Vec<String> = std::env::args().skip(1).collect();
+  let expect = args.first().map(|a| a == "--expect").unwrap_or(false);
+  if expect {
+    args.remove(0);
+  }
+
+  if !args.is_empty() {
+    let ty = args.remove(0);
+    for input in &args {
+      parse_as(&ty, input, expect);
+    }
+    return;
+  }
+
+  let stdin = std::io::stdin();
+  println!("Enter `<type> <input>`, i.e. `u32 42`. Ctrl-D to quit.");
+  loop {
+    let mut line = String::new();
+    if stdin.read_line(&mut line).expect("Failed to read line") == 0 {
+      break;
+    }
+    // Only the type is split off, so the input keeps any inner spaces.
+    let line = line.trim_end_matches(&['\n', '\r'][..]);
+    match line.find(' ') {
+      Some(idx) => parse_as(&line[..idx], &line[idx + 1..], expect),
+      None => println!("need a type and an input"),
+    }
+  }
+}
+
+fn parse_as(ty: &str, input: &str, expect: bool) {
+  match ty {
+    "i8" => parse_with::<i8>(input, expect),
+    "i16" => parse_with::<i16>(input, expect),
+    "i32" => parse_with::<i32>(input, expect),
+    "i64" => parse_with::<i64>(input, expect),
+    "i128" => parse_with::<i128>(input, expect),
+    "isize" => parse_with::<isize>(input, expect),
+    "u8" => parse_with::<u8>(input, expect),
+    "u16" => parse_with::<u16>(input, expect),
+    "u32" => parse_with::<u32>(input, expect),
+    "u64" => parse_with::<u64>(input, expect),
+    "u128" => parse_with::<u128>(input, expect),
+    "usize" => parse_with::<usize>(input, expect),
+    "f32" => parse_with::<f32>(input, expect),
+    "f64" => parse_with::<f64>(input, expect),
+    "bool" => parse_with::<bool>(input, expect),
+    "char" => parse_with::<char>(input, expect),
+    _ => println!("don't know how to parse a `{}`", ty),
+  }
+}
+
+// parse() works for anything implementing FromStr, and every FromStr has
+// its own error type: ParseIntError, ParseFloatError, ParseBoolError and
+// ParseCharError. The Debug output of each one shows its exact kind,
+// i.e. ParseIntError { kind: PosOverflow } for "256" as a u8.
+fn parse_with<T>(input: &str, expect: bool)
+  where T: std::str::FromStr + std::fmt::Debug,
+        T::Err: std::fmt::Debug + std::fmt::Display {
+  if expect {
+    // The version from the notes. expect unwraps the Ok value, and on an
+    // Err it panics with our message followed by the error's Debug output:
+    // Not a number!: ParseIntError { kind: InvalidDigit }
+    let value: T = input.parse().expect("Not a number!");
+    println!("{:?} -> {:?}", input, value);
+    return;
+  }
+
+  // The recoverable version. Matching on the Result lets us keep going
+  // after a bad input instead of taking the whole program down. Note that
+  // parse doesn't trim, so " 42" is an InvalidDigit, while "+42" is fine.
+  match input.parse::<T>() {
+    Ok(value) => println!("{:?} -> {:?}", input, value),
+    Err(e) => println!("{:?} -> error: {} ({:?})", input, e, e),
+  }
+}
+
 fn tup() {
   let tup = (500, 6.4, true);
+  show_type("tup", &tup);
   let (_, num, _) = tup;
+  show_type("num", &num);
   println!("Tuple time. Value of num is -> {}", num);
 
   // Tuples can also be indexed by the dot operator followed by the idx
   let last_val = tup.2;
+  show_type("last_val", &last_val);
   println!("Last value -> {}", last_val);
+
+  // Patterns nest the same way the tuples do.
+  let nested = ((1, 2), (3.5, 'x'));
+  show_type("nested", &nested);
+  let ((a, b), (c, d)) = nested;
+  show_type("a", &a);
+  show_type("b", &b);
+  show_type("c", &c);
+  show_type("d", &d);
+  println!("((a, b), (c, d)) -> a {} b {} c {} d {}", a, b, c, d);
+
+  // `..` skips however many fields are in the middle (or the ends), so
+  // you don't have to write out a `_` for each one. Only one `..` is
+  // allowed per tuple pattern, otherwise it'd be ambiguous.
+  let long = (1, 2, 3, 4, 5);
+  show_type("long", &long);
+  let (first, .., last) = long;
+  show_type("first", &first);
+  show_type("last", &last);
+  println!("(first, .., last) -> first {} last {}", first, last);
+  let (head, ..) = long;
+  show_type("head", &head);
+  println!("(head, ..) -> head {}", head);
+
+  // Destructuring moves fields out just like assignment does, so with a
+  // String inside the tuple is (partly) moved. `ref` binds a reference
+  // to the field instead, and the tuple stays whole.
+  let named = (String::from("duder"), 5);
+  show_type("named", &named);
+  let (ref name, count) = named;
+  show_type("name", &name);
+  show_type("count", &count);
+  println!("(ref name, count) -> name {} count {}", name, count);
+  println!("named is still usable -> {:?}", named);
+
+  // Matching against a reference to a tuple works too. The `&` in the
+  // pattern strips the reference off, which is fine for Copy fields.
+  let point = &(3, 4);
+  show_type("point", &point);
+  let &(x, y) = point;
+  show_type("x", &x);
+  show_type("y", &y);
+  println!("&(x, y) -> x {} y {}", x, y);
+  // Without the `&`, the bindings become references themselves (&i32
+  // here, SHOW_TYPES=1 shows it).
+  let (rx, ry) = point;
+  show_type("rx", &rx);
+  show_type("ry", &ry);
+  println!("(rx, ry) -> rx {} ry {}", rx, ry);
+
+  // Function parameters are patterns as well.
+  fn print_coords((x, y): (i32, i32)) {
+    show_type("x", &x);
+    show_type("y", &y);
+    println!("print_coords((x, y)) -> x {} y {}", x, y);
+  }
+  print_coords((7, 8));
+
+  // So is the bit between `for` and `in`.
+  let pairs = [(1, "one"), (2, "two"), (3, "three")];
+  show_type("pairs", &pairs);
+  for &(n, word) in pairs.iter() {
+    show_type("n", &n);
+    show_type("word", &word);
+    println!("for &(n, word) -> n {} word {}", n, word);
+  }
+  for (i, &(n, _)) in pairs.iter().enumerate() {
+    show_type("i", &i);
+    show_type("n", &n);
+    println!("for (i, &(n, _)) -> i {} n {}", i, n);
+  }
+
+  // Swapping two values without a temp: build a tuple, then take it
+  // apart in the other order.
+  let (mut left, mut right) = (1, 2);
+  (left, right) = (right, left);
+  show_type("left", &left);
+  show_type("right", &right);
+  println!("swapped -> left {} right {}", left, right);
+
+  // Returning more than one thing from a fn is just returning a tuple.
+  fn min_max(items: &[i32]) -> (i32, i32) {
+    let mut min = items[0];
+    let mut max = items[0];
+    for &item in items.iter() {
+      if item < min { min = item; }
+      if item > max { max = item; }
+    }
+    (min, max)
+  }
+  let (min, max) = min_max(&[4, 9, 1, 7]);
+  show_type("min", &min);
+  show_type("max", &max);
+  println!("min_max -> min {} max {}", min, max);
+
+  // This is the pattern the ownership notes call "obnoxious": taking
+  // ownership of a String and having to hand it back in a tuple
+  // alongside the answer, just so the caller can keep using it.
+  fn calculate_length(s: String) -> (String, usize) {
+    let length = s.len();
+    (s, length)
+  }
+  let (s, len) = calculate_length(String::from("hello"));
+  show_type("s", &s);
+  show_type("len", &len);
+  println!("calculate_length -> s {} len {}", s, len);
 }
 
 fn arr() {
@@ -84,14 +375,16 @@ fn arr() {
   // Arrays also have a fixed size. Once declared, they cannot grow or
   // shrink in size.
   let a = [1, 2, 3 ,4, 5];
+  show_type("a", &a);
 
   // They are useful when you want your data allocated on the stack
   // and not the heap, or when you want to ensure you always have a
   // fixed number of elements. They're not as flexible as a vector.
   // The vecors are dynamic and are allowed to grow and shrink in size.
+  // See arr_vs_vec()
   //
   // A good example for when to use an array is say, the months of the
-  // year. That size will never change.
+  // year. That size will never change. See months()
   //
   // As expected, values are extracted with the familiar idx op [idx]
   //
@@ -105,6 +398,141 @@ fn arr() {
   // by immediately exiting instead of allowing the access of the mem.
 }
 
+fn months() {
+  use calendar::{Month, Table};
+
+  // The array from arr() that never got written. The Month enum gives each
+  // index a name, so there's no remembering that June is 5. Table::iter
+  // walks the days in the same order as Month::ALL.
+  let days_2023 = calendar::days_in(2023);
+  for (month, days) in Month::ALL.iter().zip(days_2023.iter()) {
+    println!("{:>2} {:<9} -> {} days", *month as usize + 1, month.name(), days);
+  }
+
+  for &year in [1900, 2000, 2023, 2024].iter() {
+    let days = calendar::days_in(year);
+    // Summed straight off the Table, no array in sight.
+    let total: u32 = days.iter().sum();
+    println!("{} leap year -> {:<5} feb -> {} total -> {}",
+      year, calendar::is_leap_year(year), days[Month::February], total);
+  }
+
+  // The length is part of the type, so none of these can go wrong at runtime.
+  let days = calendar::days_in(2024);
+  println!("days table len -> {}, dec -> {}", days.len(), days[Month::December]);
+
+  // A table with some other length simply can't be indexed by a Month.
+  // This won't compile:
+  //
+  // let week = Table::new(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]);
+  // week[Month::March];
+  //
+  // error[E0308]: mismatched types
+  //   |
+  //   |   week[Month::March];
+  //   |        ^^^^^^^^^^^^ expected `usize`, found `Month`
+  let week = Table::new(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]);
+  println!("week len -> {}, first -> {}", week.len(), week[0]);
+
+  // Neither can the plain array be walked off the end with a constant
+  // index, rustc catches it before the program ever runs:
+  //
+  // calendar::NAMES[12];
+  //
+  // error: this operation will panic at runtime
+  //   |
+  //   |   calendar::NAMES[12];
+  //   |   ^^^^^^^^^^^^^^^^^^^ index out of bounds: the length is 12 but the index is 12
+
+  // The same data as a Vec. Its length only exists at runtime, so nothing
+  // stops it from growing a 13th month, and an index past the end is
+  // only found out when the code runs.
+  let mut names: Vec<&str> = calendar::NAMES.to_vec();
+  names.push("Smarch");
+  println!("vec len -> {}, names[12] -> {}", names.len(), names[12]);
+  println!("vec get(13) -> {:?}", names.get(13));
+}
+
+fn arr_vs_vec() {
+  use std::mem::{size_of, size_of_val};
+
+  // Four ways to hold five i32s:
+  // [i32; 5]   the array itself, all 5 values inline wherever it lives
+  // Vec<i32>   ptr/len/cap on the stack, the values on the heap, can grow
+  // Box<[i32]> ptr/len on the stack, the values on the heap, fixed size
+  // &[i32]     ptr/len borrowed from one of the others, owns nothing
+  let arr = [1, 2, 3, 4, 5];
+  let vec = vec![1, 2, 3, 4, 5];
+  let boxed: Box<[i32]> = vec![1, 2, 3, 4, 5].into_boxed_slice();
+  let slice: &[i32] = &arr;
+
+  println!("[i32; 5]   -> {:>2} bytes", size_of_val(&arr));
+  println!("Vec<i32>   -> {:>2} bytes", size_of_val(&vec));
+  println!("Box<[i32]> -> {:>2} bytes", size_of_val(&boxed));
+  println!("&[i32]     -> {:>2} bytes", size_of::<&[i32]>());
+
+  // Where do the five values actually live? Locals are all close together
+  // on the stack, so anything near the address of one is on the stack and
+  // anything far away came from the heap. Crude, but it's good evidence.
+  let marker = 0;
+  let here = &marker as *const i32 as usize;
+  let place = |ptr: *const i32| {
+    let dist = (ptr as usize).max(here) - (ptr as usize).min(here);
+    if dist < 64 * 1024 { "stack" } else { "heap" }
+  };
+  println!("array data  {:p} -> {}", arr.as_ptr(), place(arr.as_ptr()));
+  println!("vec data    {:p} -> {}", vec.as_ptr(), place(vec.as_ptr()));
+  println!("boxed data  {:p} -> {}", boxed.as_ptr(), place(boxed.as_ptr()));
+  println!("slice data  {:p} -> {} (same as the array)", slice.as_ptr(), place(slice.as_ptr()));
+
+  // Only the Vec can grow. Once len hits cap it has to reallocate: ask
+  // for a bigger block, copy everything over and free the old one. The
+  // allocator can sometimes just extend the block where it is, so the
+  // data pointer doesn't always move. Capacity doubles each time, so it
+  // doesn't happen often.
+  let mut growing: Vec<i32> = Vec::new();
+  let (mut last_ptr, mut last_cap) = (growing.as_ptr(), growing.capacity());
+  for i in 0..17 {
+    growing.push(i);
+    let note = if growing.capacity() == last_cap {
+      ""
+    } else if last_cap == 0 {
+      "<- first allocation"
+    } else if growing.as_ptr() == last_ptr {
+      "<- reallocated in place"
+    } else {
+      "<- reallocated, data moved"
+    };
+    println!("len {:>2} cap {:>2} {}", growing.len(), growing.capacity(), note);
+    last_ptr = growing.as_ptr();
+    last_cap = growing.capacity();
+  }
+  // Vec::with_capacity(17) would have allocated once, up front.
+
+  // Moving (or passing) an array copies every element to its new home.
+  // Moving a Vec or a Box only copies the few words on the stack, the heap
+  // data stays put. A slice is Copy, and copies its two words. (In a
+  // release build the optimizer may skip some of these copies entirely.)
+  fn data_of<T>(items: &[T]) -> *const T {
+    items.as_ptr()
+  }
+  let big = [0u8; 4096];
+  let big_before = data_of(&big);
+  let big_moved = big;
+  println!("[u8; 4096] moved: {:p} -> {:p} ({} bytes copied)",
+    big_before, data_of(&big_moved), size_of_val(&big_moved));
+
+  let vec_before = data_of(&vec);
+  let vec_moved = vec;
+  println!("Vec<i32> moved:   {:p} -> {:p} ({} bytes copied)",
+    vec_before, data_of(&vec_moved), size_of_val(&vec_moved));
+
+  let boxed_before = data_of(&boxed);
+  let boxed_moved = boxed;
+  println!("Box<[i32]> moved: {:p} -> {:p} ({} bytes copied)",
+    boxed_before, data_of(&boxed_moved), size_of_val(&boxed_moved));
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Functions
 ////////////////////////////////////////////////////////////////////////////////
@@ -154,12 +582,74 @@ fn arr() {
 //  Note if a value is not returned, we'll see that the found type
 //  is in fact an empty tuple, (). Nothing has been returned,
 //  contradicting the function definition that expects a result.
+//
+//  See expressions() to type in statements and expressions and see what
+//  each one evaluates to.
 ////////////////////////////////////////////////////////////////////////////////
 
 fn func() {
+  //expressions();
   block_eval()
 }
 
+fn expressions() {
+  // Every line is evaluated in the same scope, so lets and fns carry
+  // over from one line to the next. Lines can come from the args, i.e.
+  // cargo run -- "let x = 5;" "x + 1;"
+  // or typed in/piped through stdin after the notes' examples have run,
+  // so x, y, five() and friends are already there to play with.
+  let mut evaluator = eval::Evaluator::new();
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  if !args.is_empty() {
+    for line in &args {
+      evaluator.run(line);
+    }
+    return;
+  }
+
+  let examples = [
+    // `let y = 6;` is a statement, and a block is an expression.
+    "let x = 5;",
+    "let y = { let x = 3; x + 1 };",
+    "x",
+    "y",
+    // The semicolon turns the expression into a statement, giving ().
+    "x + 1",
+    "x + 1;",
+    "{ x + 1 }",
+    "{ x + 1; }",
+    // A block that starts a statement ends at its `}`, so this is `{ x }`
+    // followed by `-1`, not a subtraction. `({ x } - 1)` is one.
+    "{ x } - 1",
+    // No chaining lets like C's x = y = 6.
+    "let a = let b = 6;",
+    "fn five() -> i32 { 5 }",
+    "five()",
+    "fn plus_one(x: i32) -> i32 { x + 1 }",
+    "plus_one(5)",
+    // The one from the notes: the body is x + 1; which is ().
+    "fn plus_two(x: i32) -> i32 { x + 2; }",
+    "let n = if y == 4 { 1 } else { 2 };",
+    "if y { 1 } else { 2 }",
+    "if true { 1 } else { false }",
+  ];
+
+  for example in examples.iter() {
+    evaluator.run(example);
+  }
+
+  let stdin = std::io::stdin();
+  println!();
+  println!("Enter an expression or statement, i.e. `x + 1;`. Ctrl-D to quit.");
+  loop {
+    let mut line = String::new();
+    if stdin.read_line(&mut line).expect("Failed to read line") == 0 {
+      break;
+    }
+    evaluator.run(line.trim());
+  }
+}
+
 
 fn block_eval() {
   let x = 5;
@@ -170,8 +660,11 @@ fn block_eval() {
   // expression, its turned into a statement, which will not return a val.
   let y = {
     let x = 3;
+    show_type("x (inner)", &x);
     x + 1
   };
+  show_type("x", &x);
+  show_type("y", &y);
 
   println!("The value of (x, y) -> ({}, {})", x, y)
 }
@@ -193,7 +686,7 @@ fn block_eval() {
 //
 // + `else if` is available.
 // Note: if you have more than one else if, consider using a more powerful
-// match branching construct.
+// match branching construct. See matching()
 //
 // + Using if in a let statement, see let_if()
 //
@@ -202,10 +695,105 @@ fn block_eval() {
 // see loops()
 ////////////////////////////////////////////////////////////////////////////////
 fn ctl_flow() {
+  //matching()
   //let_if()
+  //iterators()
+  //liftoff()
   loops()
 }
 
+// The else if chain. Each condition is checked in order, and it's up
+// to us to make sure they don't overlap or leave gaps. Nothing checks
+// that the final else is really only catching what we think it is.
+fn classify_if(n: i32) -> String {
+  if n < 0 {
+    String::from("negative")
+  } else if n == 0 {
+    String::from("zero")
+  } else if n < 10 {
+    String::from("single digit")
+  } else if n == 42 {
+    String::from("the answer")
+  } else if n < 100 && n % 2 == 0 {
+    format!("even two digit {}", n)
+  } else if n < 100 {
+    format!("odd two digit {}", n)
+  } else {
+    String::from("big")
+  }
+}
+
+// The same thing with match. Each arm is a pattern:
+// -> literals match one value (0, 42)
+// -> ranges match a span of values (1..=9)
+// -> `n @ pattern` binds the matched value to a name to use in the arm
+// -> `if ...` after a pattern is a *guard*, an extra condition
+// -> `_` matches anything
+fn classify_match(n: i32) -> String {
+  match n {
+    i32::MIN..=-1 => String::from("negative"),
+    0 => String::from("zero"),
+    1..=9 => String::from("single digit"),
+    42 => String::from("the answer"),
+    n @ 10..=99 if n % 2 == 0 => format!("even two digit {}", n),
+    n @ 10..=99 => format!("odd two digit {}", n),
+    _ => String::from("big"),
+  }
+}
+
+fn matching() {
+  let inputs = [-40, 0, 7, 12, 42, 99, 100, 1_000];
+
+  // classify_if and classify_match, just above, give the same answers
+  // (the tests check that), but only match is checked by the compiler.
+  for &n in inputs.iter() {
+    println!("{:>5} -> {}", n, classify_match(n));
+  }
+
+  // Patterns can take apart tuples too, which is where match really pulls
+  // ahead of a chain of ifs.
+  let points: [(i32, i32); 5] = [(0, 0), (3, 0), (0, -2), (4, 4), (1, 5)];
+  for &point in points.iter() {
+    let place = match point {
+      (0, 0) => String::from("origin"),
+      (x, 0) | (0, x) => format!("on an axis, {} from the origin", x.abs()),
+      (x, y) if x == y => String::from("on the diagonal"),
+      (x, y) => format!("somewhere else, x {} y {}", x, y),
+    };
+    println!("{:?} -> {}", point, place);
+  }
+
+  // The big difference: match is checked for *exhaustiveness*. Every
+  // possible value has to be covered by some arm, or it won't compile.
+  // Leave zero out and rustc tells you exactly what's missing:
+  //
+  // let s = match n {
+  //   i32::MIN..=-1 => "negative",
+  //   1..=9 => "small",
+  //   10..=i32::MAX => "big",
+  // };
+  //
+  // error[E0004]: non-exhaustive patterns: `0_i32` not covered
+  //   |
+  //   |   let s = match n {
+  //   |                 ^ pattern `0_i32` not covered
+  //   |
+  //   = note: the matched value is of type `i32`
+  //
+  // An else if chain missing the same case compiles just fine. Guards
+  // don't count towards exhaustiveness, since the compiler can't know
+  // what they'll return, so a match full of guards still needs a `_`.
+  //
+  // match on a bool needs both true and false, with no `_` required:
+  for &b in [true, false].iter() {
+    let word = match b {
+      true => "yes",
+      false => "no",
+    };
+    println!("{} -> {}", b, word);
+  }
+}
+
 fn let_if() {
   // Remember blocks of code evaluate to the last expression in them, and
   // numbers by themselves are also expressions. Therefore, the value
@@ -216,9 +804,63 @@ fn let_if() {
   // time so it can verify the type is valid everywhere `number` is used.
   // It wouldn't be able to do that if the type of the variable `number`
   // was determed by runtime code.
-  let cond = false;
-  let number = if cond {5} else {6};
-  println!("let_if cond -> {}", number);
+  //
+  // The *values* can come from runtime code though. The condition and
+  // both arms are taken from the command line, i.e. cargo run -- true 5 6,
+  // or from a line on stdin. Whichever arm runs, number is an i32.
+  let mut words: Vec<String> = std::env::args().skip(1).collect();
+  if words.is_empty() {
+    println!("Enter a condition and two numbers, i.e. `true 5 6`:");
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line).expect("Failed to read line");
+    words = line.split_whitespace().map(String::from).collect();
+  }
+
+  // Rust won't treat 1 or "yes" as true for us, so neither do we.
+  let cond: bool = match words.first().map(|w| w.parse()) {
+    Some(Ok(cond)) => cond,
+    Some(Err(_)) => {
+      println!("the condition has to be `true` or `false`, got `{}`", words[0]);
+      return;
+    },
+    None => false,
+  };
+  let arm = |idx: usize, default: i32| -> Option<i32> {
+    match words.get(idx) {
+      Some(word) => word.parse().map_err(|_| println!("`{}` isn't an i32", word)).ok(),
+      None => Some(default),
+    }
+  };
+  let (then_val, else_val) = match (arm(1, 5), arm(2, 6)) {
+    (Some(then_val), Some(else_val)) => (then_val, else_val),
+    _ => return,
+  };
+
+  let number = if cond {then_val} else {else_val};
+  show_type("cond", &cond);
+  show_type("number", &number);
+  println!("let_if cond -> {} picks the {} arm -> {}", cond, if cond {"if"} else {"else"}, number);
+
+  // The two rules from the notes, and what breaking them looks like.
+  // Arms of different types, even if only one of them could ever run:
+  //
+  // let number = if cond {5} else {"six"};
+  //
+  // error[E0308]: `if` and `else` have incompatible types
+  //   |
+  //   |   let number = if cond {5} else {"six"};
+  //   |                         -        ^^^^^ expected integer, found `&str`
+  //   |                         |
+  //   |                         expected because of this
+  //
+  // A condition that isn't a bool:
+  //
+  // if 7 {}
+  //
+  // error[E0308]: mismatched types
+  //   |
+  //   |   if 7 {}
+  //   |      ^ expected `bool`, found integer
 }
 
 fn loops() {
@@ -230,16 +872,62 @@ fn loops() {
   //
   // break can be used to break out of a loop.
   //
+  // A loop is an expression too, and break can hand a value back out of
+  // it, like retrying something until it works and keeping the result.
+  let mut counter = 0;
+  let result = loop {
+    counter += 1;
+    if counter == 10 {
+      break counter * 2;
+    }
+  };
+  println!("loop broke out with -> {}", result);
+
   // + conditional loops with while
   //
   // while [condition] {}
   //
+  // Same as a loop with an if/break at the top, minus the noise.
+  let mut number = 3;
+  while number != 0 {
+    println!("while {}!", number);
+    number -= 1;
+  }
+  println!("while LIFTOFF");
+
+  // A plain break or continue applies to the innermost loop. To reach
+  // an outer one, give it a label ('outer) and name it in the break.
+  'outer: for x in 0..5 {
+    for y in 0..5 {
+      if y > x {
+        continue 'outer; // on to the next x
+      }
+      if x + y == 6 {
+        println!("x + y == 6 at ({}, {}), breaking out of both", x, y);
+        break 'outer;
+      }
+      if y % 2 == 1 {
+        continue; // skips odd y, stays in the inner loop
+      }
+      println!("({}, {})", x, y);
+    }
+  }
+
   // + Looping collections with for
   // Looping through things with while and indexes is error prone.
   // A better alternative is the for loop, used to execute some code
-  // for each item in a collection:
-
+  // for each item in a collection.
+  //
+  // The error prone way first. It works, but only because the 5 matches
+  // the array. Make it 6 and it panics with an index out of bounds. It's
+  // also slower, since every a[index] gets a bounds check.
   let a = [10, 20, 30, 40, 50];
+  let mut index = 0;
+  while index < 5 {
+    println!("the value is: {}", a[index]);
+    index += 1;
+  }
+
   for item in a.iter() {
     println!("the value of the thing is {}", item);
   }
@@ -260,4 +948,287 @@ fn loops() {
     println!("{}!", num);
   }
   println!("GO");
+
+  // iter() and rev() are just the start, see iterators()
+  //
+  // The same countdown with our own iterator type in place of the range,
+  // with an actual pause between numbers, is in liftoff()
+}
+
+fn liftoff() {
+  use countdown::{Countdown, FakeClock, RealClock};
+  use std::time::Duration;
+
+  // It's an iterator, so everything a range can do, it can do.
+  let evens: Vec<u32> = Countdown::new(10).step(2).collect();
+  println!("Countdown::new(10).step(2)       -> {:?}", evens);
+  let up: Vec<u32> = Countdown::new(10).step(2).rev().collect();
+  println!("Countdown::new(10).step(2).rev() -> {:?}", up);
+  println!("Countdown::new(9).step(2).len()  -> {}", Countdown::new(9).step(2).len());
+
+  // Dropped in for (1..4).rev(), the fake clock makes this instant, and
+  // still tells us how long the real thing would take.
+  let mut fake = FakeClock::new();
+  let mut ticks = Vec::new();
+  let took = Countdown::new(10).launch(&mut fake, |at, n| ticks.push((at.as_secs(), n)));
+  println!("fake clock ticks (secs, n) -> {:?}", ticks);
+  println!("fake clock countdown would take -> {:?}", took);
+
+  // The real one. Start, step and tick (in ms) can come from the args,
+  // i.e. cargo run -- 5 1 500
+  let args: Vec<u32> = std::env::args().skip(1).filter_map(|a| a.parse().ok()).collect();
+  let start = args.first().cloned().unwrap_or(3);
+  let step = args.get(1).cloned().unwrap_or(1).max(1);
+  let tick = Duration::from_millis(args.get(2).cloned().unwrap_or(1000) as u64);
+
+  let mut clock = RealClock::new();
+  let took = Countdown::new(start).step(step).tick(tick)
+    .launch(&mut clock, |_, n| println!("{}!", n));
+  println!("GO (after {:.1?})", took);
+}
+
+fn iterators() {
+  // An iterator adapter takes an iterator and gives back a new one that
+  // does something extra to each item. They're lazy: nothing happens
+  // until something like a for loop or collect() pulls items through.
+  // Every stage is collected into a Vec here so it can be printed.
+  let a = [10, 20, 30, 40, 50];
+  println!("a                  -> {:?}", a);
+
+  // enumerate pairs each item with its index, no manual counter needed.
+  let enumerated: Vec<(usize, &i32)> = a.iter().enumerate().collect();
+  println!("enumerate          -> {:?}", enumerated);
+
+  // zip walks two iterators in lockstep, stopping when either runs out.
+  let names = ["ten", "twenty", "thirty"];
+  let zipped: Vec<(&i32, &&str)> = a.iter().zip(names.iter()).collect();
+  println!("zip(names)         -> {:?}", zipped);
+
+  // chain runs one iterator then another.
+  let chained: Vec<&i32> = a.iter().chain([60, 70].iter()).collect();
+  println!("chain([60, 70])    -> {:?}", chained);
+
+  // step_by takes the first item, then every nth after it.
+  let stepped: Vec<&i32> = a.iter().step_by(2).collect();
+  println!("step_by(2)         -> {:?}", stepped);
+
+  // filter keeps items the closure says yes to, map changes each one.
+  // Chained together, each item goes through both before the next starts.
+  let filtered: Vec<&i32> = a.iter().filter(|&&x| x > 20).collect();
+  println!("filter(> 20)       -> {:?}", filtered);
+  let mapped: Vec<i32> = a.iter().filter(|&&x| x > 20).map(|x| x / 10).collect();
+  println!("  .map(/ 10)       -> {:?}", mapped);
+
+  // inspect peeks at items without changing them, which shows the order
+  // things really happen in: one item all the way through, then the next.
+  let piped: Vec<i32> = a.iter()
+    .inspect(|x| println!("  from a   {}", x))
+    .filter(|&&x| x > 20)
+    .inspect(|x| println!("  filtered {}", x))
+    .map(|x| x / 10)
+    .inspect(|x| println!("  mapped   {}", x))
+    .collect();
+  println!("filter.map piped   -> {:?}", piped);
+
+  // fold carries a running value through every item, here a sum. The
+  // closure is printed as it goes so each step is visible.
+  let total = a.iter().fold(0, |acc, x| {
+    println!("fold acc {:>3} + {}", acc, x);
+    acc + x
+  });
+  println!("fold(0, +)         -> {}", total);
+
+  // take_while stops at the first item that fails, unlike filter which
+  // keeps going.
+  let taken: Vec<&i32> = a.iter().take_while(|&&x| x < 35).collect();
+  println!("take_while(< 35)   -> {:?}", taken);
+
+  // windows and chunks come from slices rather than iterators. windows
+  // overlap, chunks don't, and the last chunk can come up short.
+  let windows: Vec<&[i32]> = a.windows(2).collect();
+  println!("windows(2)         -> {:?}", windows);
+  let chunks: Vec<&[i32]> = a.chunks(2).collect();
+  println!("chunks(2)          -> {:?}", chunks);
+  let diffs: Vec<i32> = a.windows(2).map(|w| w[1] - w[0]).collect();
+  println!("windows(2) diffs   -> {:?}", diffs);
+
+  // 1..4 stops *before* 4. 1..=4 includes it.
+  let exclusive: Vec<i32> = (1..4).collect();
+  let inclusive: Vec<i32> = (1..=4).collect();
+  println!("1..4               -> {:?}", exclusive);
+  println!("1..=4              -> {:?}", inclusive);
+  let countdown: Vec<i32> = (1..=3).rev().collect();
+  println!("(1..=3).rev()      -> {:?}", countdown);
+
+  // Put together, ranges index into the array without any risk of
+  // running off the end, the same guarantee the for loop gave us.
+  let evens_doubled: Vec<i32> = (0..a.len()).step_by(2).map(|i| a[i] * 2).collect();
+  println!("(0..len).step_by(2).map(a[i] * 2) -> {:?}", evens_doubled);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Exercises
+////////////////////////////////////////////////////////////////////////////////
+// The end of the chapter suggests a few programs to practice with, using
+// nothing but what's been covered so far: variables, data types,
+// functions, comments, if and loops.
+//
+// + Convert temperatures between Fahrenheit and Celsius (and Kelvin)
+//   See temperatures()
+// + Generate the nth Fibonacci number
+//   See fibonacci()
+// + Print the lyrics to "The Twelve Days of Christmas", taking advantage
+//   of the repetition in the song
+//   See twelve_days()
+////////////////////////////////////////////////////////////////////////////////
+fn exercises() {
+  //twelve_days()
+  //fibonacci()
+  temperatures()
+}
+
+fn temperatures() {
+  use temperature::Scale;
+
+  // From the args, i.e. cargo run -- 100C F, or a line at a time from
+  // stdin until an empty line. No target scale means show all three.
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  let convert_line = |line: &str| {
+    let (value, from, to) = match temperature::parse_request(line) {
+      Ok(request) => request,
+      Err(e) => {
+        println!("error: {}", e);
+        return;
+      },
+    };
+    for &scale in Scale::ALL.iter() {
+      if to.is_some() && to != Some(scale) {
+        continue;
+      }
+      match temperature::convert(value, from, scale) {
+        Ok(converted) => println!("{}{} -> {:.2}{}", value, from.symbol(), converted, scale.symbol()),
+        Err(e) => {
+          println!("error: {}", e);
+          return;
+        },
+      }
+    }
+  };
+
+  if !args.is_empty() {
+    convert_line(&args.join(" "));
+    return;
+  }
+
+  // Round trips for some well known temperatures, to show the float error
+  // stays inside the tolerance.
+  let knowns = [(-40.0, Scale::Celsius), (0.0, Scale::Kelvin), (98.6, Scale::Fahrenheit),
+    (100.0, Scale::Celsius), (-459.67, Scale::Fahrenheit), (1e6, Scale::Kelvin)];
+  for &(value, from) in knowns.iter() {
+    for &to in Scale::ALL.iter() {
+      match temperature::round_trip(value, from, to) {
+        Ok((there, back)) => println!("{}{} -> {}{} -> {}{} (off by {:e}, within {:e})",
+          value, from.symbol(), there, to.symbol(), back, from.symbol(), (back - value).abs(),
+          temperature::TOLERANCE),
+        Err(e) => println!("round trip failed: {}", e),
+      }
+    }
+  }
+
+  println!("Enter a temperature, i.e. `100C`, `98.6 F to C` or `300 K`. Empty line to quit.");
+  loop {
+    let mut line = String::new();
+    if std::io::stdin().read_line(&mut line).expect("Failed to read line") == 0 {
+      break;
+    }
+    if line.trim().is_empty() {
+      break;
+    }
+    convert_line(&line);
+  }
+}
+
+fn fibonacci() {
+  use std::time::Instant;
+
+  // How far each integer type gets. Signed types top out a bit sooner
+  // than unsigned ones of the same size, since they give up a bit for
+  // the sign. Each extra 8 bits buys roughly another 11 or 12 numbers.
+  for (ty, n, value) in fibonacci::largest_per_type() {
+    println!("{:<4} -> largest is F({}) = {}", ty, n, value);
+  }
+  println!();
+
+  // n can come from the args, i.e. cargo run -- 90
+  let n: u32 = std::env::args().nth(1).and_then(|a| a.parse().ok()).unwrap_or(30);
+
+  // All four agree (the tests check that). The naive one is skipped past
+  // 35 or so, as it'd take minutes (and past 45, longer than you'd want
+  // to wait).
+  let answer = fibonacci::iterative(n);
+  match answer {
+    Some(value) => println!("F({}) = {}", n, value),
+    None => println!("F({}) doesn't fit in a u128", n),
+  }
+
+  // Time each one. Function calls aren't free, each one pushes a frame on
+  // the stack, which is where all of the naive version's time goes.
+  let runs = 1000;
+  let start = Instant::now();
+  for _ in 0..runs {
+    fibonacci::iterative(n);
+  }
+  println!("iterative      -> {:>10.2?} per call", start.elapsed() / runs);
+
+  let start = Instant::now();
+  for _ in 0..runs {
+    fibonacci::memoised(n);
+  }
+  println!("memoised       -> {:>10.2?} per call", start.elapsed() / runs);
+
+  let start = Instant::now();
+  for _ in 0..runs {
+    fibonacci::fast_doubling(n);
+  }
+  println!("fast doubling  -> {:>10.2?} per call", start.elapsed() / runs);
+
+  if n <= 35 {
+    let mut calls = 0;
+    let start = Instant::now();
+    fibonacci::naive(n, &mut calls);
+    println!("naive          -> {:>10.2?} per call ({} recursive calls)", start.elapsed(), calls);
+  } else {
+    // naive(n) makes 2 * F(n + 1) - 1 calls, one per node of its tree.
+    match fibonacci::iterative(n + 1).and_then(|f| f.checked_mul(2)) {
+      Some(calls) => println!("naive          -> skipped for n > 35, it'd make {} calls", calls - 1),
+      None => println!("naive          -> skipped for n > 35"),
+    }
+  }
+}
+
+fn twelve_days() {
+  // The whole song, or just one verse with cargo run -- --day 5
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  match args.iter().position(|a| a == "--day") {
+    Some(idx) => {
+      match args.get(idx + 1).and_then(|d| d.parse::<usize>().ok()) {
+        Some(day) if (1..=twelve_days::DAYS).contains(&day) => print!("{}", twelve_days::verse(day)),
+        _ => println!("--day needs a number from 1 to {}", twelve_days::DAYS),
+      }
+    },
+    None => print!("{}", twelve_days::song()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn classify_if_and_match_agree() {
+    let edges = [i32::MIN, -1, 0, 1, 9, 10, 41, 42, 43, 98, 99, 100, i32::MAX];
+    for n in (-200..=200).chain(edges.iter().cloned()) {
+      assert_eq!(classify_if(n), classify_match(n), "{}", n);
+    }
+  }
 }
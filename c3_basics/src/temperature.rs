@@ -0,0 +1,180 @@
+////////////////////////////////////////////////////////////////////////////////
+// Exercise: convert temperatures between Fahrenheit, Celsius and Kelvin
+////////////////////////////////////////////////////////////////////////////////
+// Everything goes through Celsius: convert the input to Celsius, then
+// Celsius to whatever was asked for. That's two formulas per scale
+// instead of one for every pair.
+//
+//   F = C * 9/5 + 32      C = (F - 32) * 5/9
+//   K = C + 273.15        C = K - 273.15
+//
+// Floats aren't exact (0.1 + 0.2 != 0.3), so converting there and back
+// again won't always land on the exact same bits. It does land within
+// TOLERANCE of where it started, relative to the size of the value.
+////////////////////////////////////////////////////////////////////////////////
+
+pub const TOLERANCE: f64 = 1e-9;
+
+const ABSOLUTE_ZERO_C: f64 = -273.15;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+  Celsius,
+  Fahrenheit,
+  Kelvin,
+}
+
+impl Scale {
+  pub const ALL: [Scale; 3] = [Scale::Celsius, Scale::Fahrenheit, Scale::Kelvin];
+
+  pub fn parse(s: &str) -> Option<Scale> {
+    match s.trim_start_matches('°').to_lowercase().as_str() {
+      "c" | "celsius" => Some(Scale::Celsius),
+      "f" | "fahrenheit" => Some(Scale::Fahrenheit),
+      "k" | "kelvin" => Some(Scale::Kelvin),
+      _ => None,
+    }
+  }
+
+  pub fn symbol(self) -> &'static str {
+    match self {
+      Scale::Celsius => "°C",
+      Scale::Fahrenheit => "°F",
+      Scale::Kelvin => "K",
+    }
+  }
+}
+
+fn to_celsius(value: f64, from: Scale) -> f64 {
+  match from {
+    Scale::Celsius => value,
+    Scale::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+    Scale::Kelvin => value + ABSOLUTE_ZERO_C,
+  }
+}
+
+fn from_celsius(celsius: f64, to: Scale) -> f64 {
+  match to {
+    Scale::Celsius => celsius,
+    Scale::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+    Scale::Kelvin => celsius - ABSOLUTE_ZERO_C,
+  }
+}
+
+// Nothing gets colder than absolute zero, so anything below it is a typo.
+// The check allows for float error right at the boundary, so 0 K and
+// -459.67 °F are fine.
+pub fn convert(value: f64, from: Scale, to: Scale) -> Result<f64, String> {
+  if !value.is_finite() {
+    return Err(format!("{} isn't a temperature", value));
+  }
+  let celsius = to_celsius(value, from);
+  if celsius < ABSOLUTE_ZERO_C - TOLERANCE {
+    return Err(format!("{}{} is below absolute zero", value, from.symbol()));
+  }
+  Ok(from_celsius(celsius, to))
+}
+
+pub fn close_enough(a: f64, b: f64) -> bool {
+  (a - b).abs() <= TOLERANCE * a.abs().max(b.abs()).max(1.0)
+}
+
+// Converts there and back, giving both legs if it came back within
+// TOLERANCE of where it started.
+pub fn round_trip(value: f64, from: Scale, to: Scale) -> Result<(f64, f64), String> {
+  let there = convert(value, from, to)?;
+  let back = convert(there, to, from)?;
+  if close_enough(value, back) {
+    Ok((there, back))
+  } else {
+    Err(format!("{}{} came back as {}{}", value, from.symbol(), back, from.symbol()))
+  }
+}
+
+// Reads things like "100C", "100 C", "-40 f to c", "300K F" or "98.6°F".
+// Gives back the value, its scale, and the scale to convert to if one
+// was asked for.
+pub fn parse_request(input: &str) -> Result<(f64, Scale, Option<Scale>), String> {
+  let words: Vec<&str> = input.split_whitespace()
+    .filter(|w| !w.eq_ignore_ascii_case("to"))
+    .collect();
+  if words.is_empty() {
+    return Err(String::from("nothing to convert"));
+  }
+
+  // The scale is either stuck to the end of the number or the next word.
+  let first = words[0];
+  let split = first.trim_end_matches(|c: char| c.is_alphabetic() || c == '°').len();
+  let (number, stuck) = first.split_at(split);
+  let (scale, rest) = if stuck.is_empty() {
+    (words.get(1).cloned().unwrap_or(""), &words[words.len().min(2)..])
+  } else {
+    (stuck, &words[1..])
+  };
+
+  let value: f64 = number.parse()
+    .map_err(|_| format!("`{}` isn't a number", if number.is_empty() { first } else { number }))?;
+  let from = Scale::parse(scale)
+    .ok_or_else(|| format!("`{}` isn't a scale, use C, F or K", scale))?;
+  let to = match rest.first() {
+    Some(word) => Some(Scale::parse(word).ok_or_else(|| format!("`{}` isn't a scale, use C, F or K", word))?),
+    None => None,
+  };
+  if rest.len() > 1 {
+    return Err(format!("didn't expect `{}`", rest[1..].join(" ")));
+  }
+  Ok((value, from, to))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn known_values_round_trip() {
+    // The same temperature in each scale, from the textbook.
+    let known = [
+      (-273.15, -459.67, 0.0),
+      (-40.0, -40.0, 233.15),
+      (0.0, 32.0, 273.15),
+      (37.0, 98.6, 310.15),
+      (100.0, 212.0, 373.15),
+    ];
+    for &(c, f, k) in known.iter() {
+      for &from in Scale::ALL.iter() {
+        for &to in Scale::ALL.iter() {
+          let pick = |s| match s {
+            Scale::Celsius => c,
+            Scale::Fahrenheit => f,
+            Scale::Kelvin => k,
+          };
+          let (there, back) = round_trip(pick(from), from, to).unwrap();
+          assert!(close_enough(there, pick(to)), "{:?} {:?} -> {:?}: {}", c, from, to, there);
+          assert!(close_enough(back, pick(from)), "{:?} {:?} <- {:?}: {}", c, from, to, back);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn below_absolute_zero_is_rejected() {
+    assert!(convert(-273.16, Scale::Celsius, Scale::Kelvin).is_err());
+    assert!(convert(-460.0, Scale::Fahrenheit, Scale::Celsius).is_err());
+    assert!(convert(-0.01, Scale::Kelvin, Scale::Fahrenheit).is_err());
+    assert!(round_trip(-1.0, Scale::Kelvin, Scale::Celsius).is_err());
+    // Right on the boundary is fine.
+    assert!(convert(0.0, Scale::Kelvin, Scale::Celsius).is_ok());
+    assert!(convert(-459.67, Scale::Fahrenheit, Scale::Kelvin).is_ok());
+  }
+
+  #[test]
+  fn parse_request_forms() {
+    assert_eq!(parse_request("100C"), Ok((100.0, Scale::Celsius, None)));
+    assert_eq!(parse_request("-40 f to c"), Ok((-40.0, Scale::Fahrenheit, Some(Scale::Celsius))));
+    assert_eq!(parse_request("300K F"), Ok((300.0, Scale::Kelvin, Some(Scale::Fahrenheit))));
+    assert!(parse_request("").is_err());
+    assert!(parse_request("hot").is_err());
+    assert!(parse_request("100 X").is_err());
+    assert!(parse_request("100 C F K").is_err());
+  }
+}
@@ -0,0 +1,121 @@
+////////////////////////////////////////////////////////////////////////////////
+// Exercise: print the lyrics to "The Twelve Days of Christmas"
+////////////////////////////////////////////////////////////////////////////////
+// Nothing but what arr() and loops() cover: two fixed arrays of twelve,
+// one for the ordinals and one for the gifts, and a for loop over a
+// reversed range inside another for loop. Each verse counts its gifts
+// back down from that day's to the partridge.
+//
+// The tests check song() against a separate copy of the lyrics, kept in
+// twelve_days.txt, so it's compared with something it didn't generate
+// itself.
+////////////////////////////////////////////////////////////////////////////////
+
+pub const DAYS: usize = 12;
+
+const ORDINALS: [&str; DAYS] = [
+  "first", "second", "third", "fourth", "fifth", "sixth",
+  "seventh", "eighth", "ninth", "tenth", "eleventh", "twelfth",
+];
+
+const GIFTS: [&str; DAYS] = [
+  "a partridge in a pear tree",
+  "Two turtle doves",
+  "Three French hens",
+  "Four calling birds",
+  "Five golden rings",
+  "Six geese a-laying",
+  "Seven swans a-swimming",
+  "Eight maids a-milking",
+  "Nine ladies dancing",
+  "Ten lords a-leaping",
+  "Eleven pipers piping",
+  "Twelve drummers drumming",
+];
+
+// day counts from 1, like the song does.
+pub fn verse(day: usize) -> String {
+  assert!((1..=DAYS).contains(&day), "there are only {} days of Christmas", DAYS);
+
+  let mut verse = format!("On the {} day of Christmas my true love sent to me\n", ORDINALS[day - 1]);
+  for gift in (0..day).rev() {
+    // The partridge only gets its "And" once there's something before it,
+    // and on the first day it starts the line so needs a capital.
+    if gift == 0 && day == 1 {
+      verse.push_str(&GIFTS[0][..1].to_uppercase());
+      verse.push_str(&GIFTS[0][1..]);
+    } else if gift == 0 {
+      verse.push_str("And ");
+      verse.push_str(GIFTS[0]);
+    } else {
+      verse.push_str(GIFTS[gift]);
+    }
+    verse.push('\n');
+  }
+  verse
+}
+
+pub fn song() -> String {
+  let mut song = String::new();
+  for day in 1..=DAYS {
+    if day > 1 {
+      song.push('\n');
+    }
+    song.push_str(&verse(day));
+  }
+  song
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const GOLDEN: &str = include_str!("../twelve_days.txt");
+
+  // The first line where song() and GOLDEN disagree, if there is one, as
+  // (line number, ours, golden).
+  fn check() -> Option<(usize, String, String)> {
+    let ours = song();
+    let mut golden_lines = GOLDEN.lines();
+    for (i, line) in ours.lines().enumerate() {
+      match golden_lines.next() {
+        Some(expected) if expected == line => continue,
+        Some(expected) => return Some((i + 1, line.to_string(), expected.to_string())),
+        None => return Some((i + 1, line.to_string(), String::from("<end of golden copy>"))),
+      }
+    }
+    if let Some(extra) = golden_lines.next() {
+      return Some((ours.lines().count() + 1, String::from("<end of song>"), extra.to_string()));
+    }
+    // Every line matched, so anything left is in the line endings.
+    if ours != GOLDEN {
+      return Some((ours.lines().count(), format!("{:?}", ours.lines().last()),
+        String::from("the same text, but different trailing newlines")));
+    }
+    None
+  }
+
+  #[test]
+  fn song_matches_golden_copy() {
+    if let Some((line, ours, golden)) = check() {
+      panic!("differs from the golden copy at line {}\n  ours:   {}\n  golden: {}", line, ours, golden);
+    }
+  }
+
+  #[test]
+  fn first_and_last_verses() {
+    assert_eq!(verse(1), "On the first day of Christmas my true love sent to me\n\
+      A partridge in a pear tree\n");
+    let twelfth = verse(12);
+    assert!(twelfth.starts_with("On the twelfth day of Christmas my true love sent to me\n\
+      Twelve drummers drumming\n"));
+    assert!(twelfth.ends_with("Two turtle doves\nAnd a partridge in a pear tree\n"));
+    assert_eq!(twelfth.lines().count(), 13);
+  }
+
+  #[test]
+  #[should_panic(expected = "there are only 12 days of Christmas")]
+  fn no_thirteenth_day() {
+    verse(13);
+  }
+}
@@ -0,0 +1,127 @@
+////////////////////////////////////////////////////////////////////////////////
+// Borrowing
+////////////////////////////////////////////////////////////////////////////////
+// calc_len, change and no_dangle from refs(), as plain functions instead
+// of closures so they can be reused and tested.
+//
+// Each one asks for as little as it needs:
+// + calc_len only reads the text, so it takes &str.
+// + change appends to the text, which needs the String itself, so it
+//   takes &mut String. A &mut str could only change bytes in place.
+// + no_dangle hands back a String it owns, and will build it from any
+//   kind of text through AsRef<str>.
+////////////////////////////////////////////////////////////////////////////////
+
+/// The length of `s` in bytes.
+///
+/// Takes `&str` rather than `&String`, so literals and slices work as
+/// well as Strings:
+///
+/// ```
+/// use c4_ownership::borrowing::calc_len;
+///
+/// let duderington = String::from("duderington");
+/// assert_eq!(calc_len(&duderington), 11);
+/// assert_eq!(calc_len("duder"), 5);
+/// assert_eq!(calc_len(&duderington[..5]), 5);
+/// // Bytes, not chars.
+/// assert_eq!(calc_len("é"), 2);
+/// ```
+pub fn calc_len(s: &str) -> usize {
+  s.len()
+}
+
+/// Appends `" fu."` to `some_str`.
+///
+/// ```
+/// use c4_ownership::borrowing::change;
+///
+/// let mut s = String::from("mutref");
+/// change(&mut s);
+/// change(&mut s);
+/// assert_eq!(s, "mutref fu. fu.");
+/// ```
+pub fn change(some_str: &mut String) {
+  some_str.push_str(" fu.");
+}
+
+/// A new String holding `text`, owned by the caller.
+///
+/// Nothing is borrowed from inside the function, so there's nothing to
+/// dangle. Anything that can be seen as a `&str` works:
+///
+/// ```
+/// use c4_ownership::borrowing::no_dangle;
+///
+/// assert_eq!(no_dangle("no dangle"), "no dangle");
+/// assert_eq!(no_dangle(String::from("owned")), "owned");
+/// let s = String::from("borrowed");
+/// let mut copy = no_dangle(&s);
+/// copy.push('!');
+/// assert_eq!(s, "borrowed");
+/// assert_eq!(copy, "borrowed!");
+/// ```
+pub fn no_dangle<S: AsRef<str>>(text: S) -> String {
+  String::from(text.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn calc_len_of_a_literal() {
+    assert_eq!(calc_len(""), 0);
+    assert_eq!(calc_len("duder"), 5);
+  }
+
+  #[test]
+  fn calc_len_of_a_string_ref() {
+    let s = String::from("duderington");
+    assert_eq!(calc_len(&s), 11);
+  }
+
+  #[test]
+  fn calc_len_of_a_sub_slice() {
+    let s = String::from("duderington");
+    assert_eq!(calc_len(&s[5..]), 6);
+    assert_eq!(calc_len(&s[..0]), 0);
+  }
+
+  #[test]
+  fn calc_len_counts_bytes() {
+    assert_eq!(calc_len("é"), 2);
+    assert_eq!(calc_len("日本"), 6);
+    assert_eq!(calc_len("👍"), 4);
+  }
+
+  #[test]
+  fn change_appends_every_time() {
+    let mut s = String::new();
+    change(&mut s);
+    assert_eq!(s, " fu.");
+    change(&mut s);
+    change(&mut s);
+    assert_eq!(s, " fu. fu. fu.");
+  }
+
+  #[test]
+  fn no_dangle_from_a_str() {
+    assert_eq!(no_dangle("no dangle"), "no dangle");
+  }
+
+  #[test]
+  fn no_dangle_from_a_string() {
+    assert_eq!(no_dangle(String::from("owned")), "owned");
+  }
+
+  #[test]
+  fn no_dangle_leaves_the_callers_string_alone() {
+    let s = String::from("borrowed");
+    let mut copy = no_dangle(&s);
+    copy.push_str(" and changed");
+    assert_eq!(s, "borrowed");
+    assert_eq!(copy, "borrowed and changed");
+    assert_ne!(s.as_ptr(), copy.as_ptr());
+  }
+}
@@ -0,0 +1,4 @@
+// The parts of the ownership lessons that are worth reusing, so they can
+// be called from main.rs and checked with `cargo test`.
+pub mod borrowing;
+pub mod words;
@@ -1,8 +1,22 @@
+extern crate c4_ownership;
+
+use c4_ownership::borrowing::{calc_len, change, no_dangle};
+use c4_ownership::words;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 fn main() {
   //scope()
+  //drop_order()
   //moves_and_mem();
+  //copy_vs_clone()
+  //tuple_vs_ref()
   //refs()
-  slices()
+  //refcell()
+  //lifetimes()
+  //layout()
+  //slices()
+  slice_boundaries()
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -63,6 +77,9 @@ fn main() {
 // ownership exists can help explain why it works the way that it does.
 // !!
 //
+// The "known, fixed size" of everything on the stack can be checked with
+// std::mem::size_of. See layout()
+//
 // [Ownership Rules]
 // There exist 3 very important rules to ownership in Rust:
 //
@@ -130,8 +147,100 @@ fn scope() {
 
   // Why can Strings be mutated but literals cannot? Difference is
   // how they deal with memory.
+  //
+  // Exactly when each value goes out of scope and gets dropped can be
+  // watched with a type that prints from its drop. See drop_order()
+}
+
+// Says so when it's created and when it's dropped, so we can see exactly
+// when Rust cleans each value up.
+struct Noisy(&'static str);
+
+impl Noisy {
+  fn new(name: &'static str) -> Noisy {
+    println!("  create {}", name);
+    Noisy(name)
+  }
+}
+
+impl Drop for Noisy {
+  fn drop(&mut self) {
+    println!("  drop {}", self.0);
+  }
 }
 
+// A struct drops itself first, then each of its fields in the order
+// they're declared.
+struct Pair {
+  first: Noisy,
+  second: Noisy,
+}
+
+impl Drop for Pair {
+  fn drop(&mut self) {
+    println!("  drop Pair({}, {})", self.first.0, self.second.0);
+  }
+}
+
+fn drop_order() {
+  println!("nested blocks:");
+  let _outer = Noisy::new("outer");
+  {
+    let _inner = Noisy::new("inner");
+    println!("  end of inner block");
+  } // inner is dropped here, outer is still alive
+  println!("  after inner block");
+
+  println!("reverse declaration order:");
+  {
+    let _a = Noisy::new("a");
+    let _b = Noisy::new("b");
+    let _c = Noisy::new("c");
+    println!("  end of block");
+  } // dropped c, b, a. The reverse of creation, like popping off a stack
+
+  println!("shadowing:");
+  {
+    let x = Noisy::new("x #1");
+    println!("  x is {}", x.0);
+    let x = Noisy::new("x #2");
+    println!("  end of block, x is {}", x.0);
+  } // shadowing doesn't drop x #1, it just hides the name. Both go here
+
+  println!("struct fields:");
+  {
+    let _pair = Pair { first: Noisy::new("first"), second: Noisy::new("second") };
+    println!("  end of block");
+  }
+
+  println!("temporaries:");
+  {
+    // A value that's never bound to a name lives until the end of the
+    // statement it was made in, the `;`.
+    println!("  length of temp -> {}", Noisy::new("temp").0.len());
+    // Binding to _ doesn't bind at all, so that's dropped right away too.
+    let _ = Noisy::new("underscore");
+    // Where _name *is* a binding, and lives to the end of the block.
+    let _name = Noisy::new("_name");
+    println!("  end of block");
+  }
+
+  println!("moves:");
+  {
+    let moved = Noisy::new("moved");
+    let new_owner = moved;
+    println!("  moved into new_owner");
+    takes_noisy(new_owner);
+    println!("  back from takes_noisy, nothing left to drop here");
+  } // neither moved nor new_owner own anything now, so no drop
+
+  println!("end of drop_order:");
+} // outer
+
+fn takes_noisy(noisy: Noisy) {
+  println!("  takes_noisy got {}", noisy.0);
+} // the parameter owns it now, so it's dropped at the end of the fn
+
 fn moves_and_mem() {
   // With string literals, we know the contents of the string at compile
   // time, so the text is literally hardcoded into the executable,
@@ -294,6 +403,7 @@ fn moves_and_mem() {
   // any group of simple scalar values can be Copy, and nothing that
   // requires allocation or is some form of resource is Copy.
   // -> ints, bools, floats, tuples (only if they contain also Copys).
+  // See copy_vs_clone()
   //
   // + Ownership and Functions]
   // Semantics for passing a value to a function are similar to
@@ -334,38 +444,202 @@ println!("{} ", x); // This is fine, because it was a copy.
 // data resulting from the body of the fn that we may want to return
 // as well.
 //
-// It's possible to return multiple values using a tuple.
-// But it's still obnoxious to constantly pass back a ton of stuff.
+// It's possible to return multiple values using a tuple, like
+// calculate_length does. But it's still obnoxious to constantly pass back
+// a ton of stuff. See tuple_vs_ref()
 //
 // Rust has a way to address this, and its called references!!!
 //
 ////////////////////////////////////////////////////////////////////////////////
 }
 
+// Copy: a plain copy of the bits, and the old variable stays usable.
+// Every Copy type has to be Clone too.
+#[derive(Clone, Copy, Debug)]
+struct Point {
+  x: i32,
+  y: i32,
+}
+
+// Clone only: the String inside owns heap memory, so copying the bits
+// would give two owners of the same buffer. clone() makes a new one.
+#[derive(Clone, Debug)]
+struct Named {
+  name: String,
+}
+
+// Neither, since nothing was derived.
+struct Plain;
+
+fn copy_vs_clone() {
+  // A trait bound is a promise the compiler checks at every call site.
+  // duplicate only compiles for types that are Copy, so it can use x
+  // twice. duplicate_clone takes anything Clone, but has to say so with
+  // an explicit clone().
+  fn duplicate<T: Copy>(x: T) -> (T, T) {
+    (x, x)
+  }
+
+  fn duplicate_clone<T: Clone>(x: &T) -> (T, T) {
+    (x.clone(), x.clone())
+  }
+
+  let p = Point { x: 1, y: 2 };
+  println!("duplicate(p) -> {:?}, p is still {:?}", duplicate(p), p);
+  let n = Named { name: String::from("ferris") };
+  println!("duplicate_clone(&n) -> {:?}", duplicate_clone(&n));
+  // duplicate(n); // COMPILE ERROR! the trait `Copy` is not implemented
+  println!();
+
+  // A generic fn can't ask "is T Copy?" and do something different
+  // either way, a bound is a yes or the code doesn't compile. But method
+  // lookup can: it tries the receiver as is first, then one deref at a
+  // time, and takes the first impl whose bounds hold. So the same .kind()
+  // call lands on the Copy impl if it can, then Clone, then neither.
+  // This only works on concrete types, which is why it's a macro.
+  use std::marker::PhantomData;
+
+  struct Probe<T>(PhantomData<T>);
+
+  trait IsCopy {
+    fn kind(&self) -> &'static str { "Copy" }
+  }
+  impl<T: Copy> IsCopy for &&Probe<T> {}
+
+  trait IsClone {
+    fn kind(&self) -> &'static str { "Clone only" }
+  }
+  impl<T: Clone> IsClone for &Probe<T> {}
+
+  trait IsNeither {
+    fn kind(&self) -> &'static str { "neither" }
+  }
+  impl<T> IsNeither for Probe<T> {}
+
+  macro_rules! kinds {
+    ($($t:ty),*) => {
+      vec![$((stringify!($t), (&&&Probe::<$t>(PhantomData)).kind())),*]
+    };
+  }
+
+  let kinds = kinds!(i32, bool, f64, char, (i32, bool), [u8; 4], &str,
+    String, (i32, String), Vec<i32>, Box<i32>, &mut i32,
+    Point, Named, Plain, Noisy);
+  for (ty, kind) in kinds {
+    println!("{:<14} {}", ty, kind);
+  }
+
+  // &str is Copy, it's only a pointer and a length and copying it doesn't
+  // copy the text. &mut i32 is neither, since copying it would make two
+  // mutable refs to the same thing (see refs()).
+  //
+  // Noisy implements Drop, so it can't be Copy. Copying the bits would
+  // mean drop running once for every copy. Deriving it anyway:
+  //
+  // #[derive(Clone, Copy)]
+  // struct Handle(u32);
+  //
+  // impl Drop for Handle {
+  //   fn drop(&mut self) {}
+  // }
+  //
+  // error[E0184]: the trait `Copy` cannot be implemented for this type; the type has a destructor
+  //  --> src/main.rs:2:8
+  //   |
+  // 1 | #[derive(Clone, Copy)]
+  //   |                 ---- in this derive macro expansion
+  // 2 | struct Handle(u32);
+  //   |        ^^^^^^ `Copy` not allowed on types with destructors
+}
+
+// Counts every allocation the program makes by sitting in front of the
+// system allocator, so tuple_vs_ref() can check how many happened in
+// between two points. It's installed for the whole binary, so every
+// lesson here (drop_order(), layout(), ...) runs on top of it. It only
+// adds a counter, nothing about when or where things are allocated or
+// dropped changes.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    System.realloc(ptr, layout, new_size)
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+// Runs f, giving back what it returned and how many allocations it made.
+fn count_allocs<F: FnOnce() -> R, R>(f: F) -> (R, usize) {
+  let before = ALLOCATIONS.load(Ordering::Relaxed);
+  let result = f();
+  (result, ALLOCATIONS.load(Ordering::Relaxed) - before)
+}
+
+fn tuple_vs_ref() {
+  // Three ways to get the length of a String and still have the String
+  // afterwards. Nothing is printed inside count_allocs, since printing
+  // can allocate too.
+
+  // 1) Hand it over and get it back. The String has to be rebound on
+  //    the way out, and the call site has to unpack a tuple.
+  let s = String::from("tuple");
+  let ((s, len), allocs) = count_allocs(|| calculate_length(s));
+  println!("let (s, len) = calculate_length(s);        len {} allocs {}", len, allocs);
+
+  // 2) Hand over a clone and keep the original. Reads a little better,
+  //    but the clone is a whole new heap buffer for the text.
+  let ((_, len), allocs) = count_allocs(|| calculate_length(s.clone()));
+  println!("let (_, len) = calculate_length(s.clone()); len {} allocs {}", len, allocs);
+
+  // 3) Lend it. s never leaves, so there's nothing to give back.
+  let (len, allocs) = count_allocs(|| calculate_length_borrowed(&s));
+  println!("let len = calculate_length_borrowed(&s);   len {} allocs {}", len, allocs);
+  println!("s is still {}", s);
+
+  // Moving a String into a fn and back out doesn't allocate either, it
+  // only copies the ptr/len/cap on the stack (see moves_and_mem()). So
+  // the tuple version only costs an allocation once we reach for clone()
+  // to get around it. The real cost of 1) is the call site: the String
+  // has to be threaded through every call, and forgetting to rebind it
+  // means it's gone.
+}
+
 fn refs() {
   // [References and Borrowing]
   // The issue with the returning tuple code we've seen elsewhere in
-  // the ownership section is that we have to return the String to
-  // the calling function so we can still use the String after the call.
+  // the ownership section (calculate_length, see tuple_vs_ref()) is that
+  // we have to return the String to the calling function so we can still
+  // use the String after the call.
   // Here we define calculate_length so that it uses a *reference* to
   // an object as a param instead of taking ownership of the value.
 
-  let calc_len = |s: &String| -> usize {
-    s.len()
-  };
-
+  // calc_len, change and no_dangle live in borrowing.rs.
   let duderington = String::from("duderington");
   println!("the length of the string. -> {}", calc_len(&duderington));
 
   // First, all the tuple code in the variable declaration is gone.
   // We pass the string into the function as &duderington, and in the
-  // definition, we take &String rather than String.
+  // definition, we take &str rather than String.
   //
   // The ampersands are *references*, and they allow you to refer to
   // some value without taking ownership over it.
-  // s inside of calc_len becomes a pointer to the String struct bound
-  // to duderington, which itself contains a ptr to the actual string
-  // data on the heap.
+  // &duderington is a pointer to the String struct bound to duderington,
+  // which itself contains a ptr to the actual string data on the heap.
+  // s inside of calc_len is a &str made from it, pointing straight at
+  // that heap data.
   //
   // A closer look at the function call:
   //
@@ -386,15 +660,35 @@ fn refs() {
   // need to return the vals in order to give back ownership, since
   // we never had ownership in the first place.
   //
+  // + &str vs &String
+  // The book writes calc_len as taking &String, but &str is the better
+  // choice. A &String can only ever point at a whole String, so a literal
+  // would have to be copied into a String first just to be measured:
+  //
+  // fn calc_len(s: &String) -> usize { s.len() }
+  // calc_len("hi");
+  //
+  // error[E0308]: mismatched types
+  //   |
+  //   |     calc_len("hi");
+  //   |     -------- ^^^^ expected `&String`, found `&str`
+  //
+  // A &str can point at a literal, a part of a String, or the whole of
+  // one. And a &String passed where a &str is wanted turns into one for
+  // free (deref coercion), so callers holding a String lose nothing:
+  println!("calc_len(\"literal\") -> {}", calc_len("literal"));
+  println!("calc_len(&duderington[..5]) -> {}", calc_len(&duderington[..5]));
+  // &String is also one more pointer to follow: it points at the
+  // ptr/len/cap on the stack, which points at the text. &str points
+  // straight at the text (see layout()).
+  //
   // What happens if we try to mutate something we borrowed?
   // Compiler errors. As vars are immutable by default, so are refs.
   // We are not allowed to modify a vanilla ref.
   //
   // + Mutable refs
-  let change = |some_str: &mut String| {
-    some_str.push_str(" fu.");
-  };
-
+  // change takes a &mut String. It needs the String, not a &mut str,
+  // since pushing onto it may mean a bigger heap buffer.
   let mut s = String::from("mutref");
   change(&mut s);
   println!("mutref string after stuff: [{}]", s);
@@ -463,7 +757,7 @@ fn refs() {
   */
 
   // This will complain about something we haven't covered yet:
-  // *lifetimes*.
+  // *lifetimes*. See lifetimes()
 
   // The key here is the return type contains a borrowed value, but there
   // is no value for it to be borrowed from.
@@ -474,12 +768,7 @@ fn refs() {
 
   // The solution here is to return the String directly, thus transfering
   // ownership via a move to the caller of the fn.
-  let no_dangle = || -> String {
-    let s = String::from("no dangle");
-    s
-  };
-
-  let ndstr = no_dangle();
+  let ndstr = no_dangle("no dangle");
   println!("{}", ndstr);
 
   // Recapping the rules of refs:
@@ -487,6 +776,463 @@ fn refs() {
   //   -> One mutable ref
   //   -> Any number of immutable refs
   // 2) References must always be valid
+  //
+  // The compiler checks all of this before the program ever runs. For
+  // the same rules checked while it runs, see refcell()
+}
+
+fn refcell() {
+  // Everything in refs() is checked at compile time, and costs nothing
+  // once the program is running. Sometimes the compiler can't prove a
+  // borrow is fine even though it is, usually when a value is shared
+  // between several owners. RefCell moves the checking to runtime
+  // instead: it keeps a count of the borrows that are live, and each
+  // borrow checks the count before handing out a ref. It's what the
+  // compiler does for you in refs(), done by hand.
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  // borrow() is &, borrow_mut() is &mut, and the same rules apply.
+  // Any number of borrows at once:
+  let cell = RefCell::new(String::from("cell"));
+  let r1 = cell.borrow();
+  let r2 = cell.borrow();
+  println!("two borrows -> {} and {}", r1, r2);
+
+  // But no borrow_mut while they're live. Where refs() fails to compile,
+  // this fails when it runs. try_borrow_mut gives back the error instead
+  // of panicking like borrow_mut would.
+  match cell.try_borrow_mut() {
+    Ok(_) => println!("try_borrow_mut -> ok?"),
+    Err(e) => println!("try_borrow_mut while borrowed -> {} ({:?})", e, e),
+  }
+
+  // The borrows end when the Refs are dropped, like r1 going out of
+  // scope in refs(). Then it's fine.
+  drop(r1);
+  drop(r2);
+  cell.try_borrow_mut().expect("nothing is borrowed").push_str(" changed");
+  println!("try_borrow_mut after drop -> {}", cell.borrow());
+
+  // And the other way around, nothing else while a borrow_mut is live.
+  {
+    let mut w = cell.borrow_mut();
+    w.push('!');
+    match cell.try_borrow() {
+      Ok(_) => println!("try_borrow -> ok?"),
+      Err(e) => println!("try_borrow while mutably borrowed -> {} ({:?})", e, e),
+    }
+  }
+  println!();
+
+  // + Rc<RefCell<_>>
+  // Rc gives a value more than one owner, freeing it when the last one is
+  // dropped. But the owners only get & out of it, so on its own nothing
+  // could change the value. Wrapping it in a RefCell lets any of them
+  // borrow_mut, one at a time.
+  let shared = Rc::new(RefCell::new(String::from("shared")));
+  let a = Rc::clone(&shared);
+  let b = Rc::clone(&shared);
+  a.borrow_mut().push_str(" by a");
+  b.borrow_mut().push_str(" and b");
+  println!("owners -> {}", Rc::strong_count(&shared));
+  println!("shared -> {}", shared.borrow());
+  drop(a);
+  drop(b);
+  println!("owners after drops -> {}", Rc::strong_count(&shared));
+
+  // The catch is that mistakes become panics instead of compile errors:
+  //
+  // let r = shared.borrow();
+  // shared.borrow_mut(); // panics: RefCell already borrowed
+  //
+  // and every borrow pays for a check. So reach for plain refs first, and
+  // RefCell when the compiler can't see that the borrows are fine.
+}
+
+fn layout() {
+  // Every type has a size (how many bytes a value takes up) and an
+  // alignment (the address of a value has to be a multiple of it). These
+  // are the numbers the compiler uses to lay things out on the stack.
+  use std::mem::{align_of, size_of, size_of_val};
+
+  fn row<T>(name: &str) {
+    println!("{:<18} size {:>2}  align {:>2}", name, size_of::<T>(), align_of::<T>());
+  }
+
+  row::<bool>("bool");
+  row::<char>("char");
+  row::<(i32, f64, bool)>("(i32, f64, bool)");
+  row::<[i32; 5]>("[i32; 5]");
+  row::<String>("String");
+  row::<&String>("&String");
+  row::<&str>("&str");
+  row::<Option<&String>>("Option<&String>");
+  println!();
+
+  // A char is 4 bytes, not 1, since it has to hold any unicode scalar.
+  //
+  // The tuple from tup() holds 4 + 8 + 1 = 13 bytes of data, but takes up
+  // 16. The f64 has to sit on an 8 byte boundary and the whole tuple has
+  // to be a multiple of 8 too, so the leftover bytes are padding. Rust is
+  // free to reorder the fields to keep the padding down, which is why the
+  // offsets don't follow the order they were written in.
+  let offsets = [
+    ("0: i32", std::mem::offset_of!((i32, f64, bool), 0), size_of::<i32>()),
+    ("1: f64", std::mem::offset_of!((i32, f64, bool), 1), size_of::<f64>()),
+    ("2: bool", std::mem::offset_of!((i32, f64, bool), 2), size_of::<bool>()),
+  ];
+  let mut used = 0;
+  for &(field, offset, size) in offsets.iter() {
+    println!("(i32, f64, bool).{:<8} offset {:>2}  size {}", field, offset, size);
+    used += size;
+  }
+  println!("(i32, f64, bool) padding -> {} bytes", size_of::<(i32, f64, bool)>() - used);
+  println!();
+
+  // Arrays have no padding between elements, they're packed back to back,
+  // element n lives at n * size_of::<i32>().
+  let a = [1, 2, 3, 4, 5];
+  for (i, item) in a.iter().enumerate() {
+    let offset = item as *const i32 as usize - a.as_ptr() as usize;
+    println!("a[{}] offset {:>2}", i, offset);
+  }
+  println!();
+
+  // The String is the ptr/len/cap group from moves_and_mem(): three words
+  // on the stack, no matter how much text is sitting on the heap.
+  let mut s = String::from("hello");
+  s.reserve(10); // so len and cap aren't the same
+  println!("String ptr -> {:p}", s.as_ptr());
+  println!("String len -> {}", s.len());
+  println!("String cap -> {}", s.capacity());
+  println!("3 words -> {} bytes", 3 * size_of::<usize>());
+  println!();
+
+  // &String is a plain pointer to that group on the stack, one word.
+  // &str is a *fat* pointer: a pointer to the bytes *and* a length, so it
+  // is two words. size_of_val looks through the ref at what it points to.
+  let r = &s;
+  let slice: &str = &s[..];
+  println!("&String -> {} bytes, points at {} bytes", size_of::<&String>(), size_of_val(r));
+  println!("&str -> {} bytes, points at {} bytes", size_of::<&str>(), size_of_val(slice));
+
+  // A reference can never be null, so None gets to use the null pointer
+  // and Option<&String> costs nothing over &String.
+  println!("Option<&String> == &String -> {}", size_of::<Option<&String>>() == size_of::<&String>());
+}
+
+// Holds on to part of a string it doesn't own. The 'a says an Excerpt
+// can't outlive the text its part points into.
+struct Excerpt<'a> {
+  part: &'a str,
+}
+
+impl<'a> Excerpt<'a> {
+  // No lifetimes written on the return type: when a method takes &self,
+  // the result is assumed to borrow from self (rule 3 below).
+  fn announce(&self, announcement: &str) -> &str {
+    println!("  attention please: {}", announcement);
+    self.part
+  }
+}
+
+fn lifetimes() {
+  // Every reference has a *lifetime*, the stretch of code it's valid for.
+  // Most of the time the compiler works them out on its own, the same way
+  // it works out most types. It only asks for them to be written when it
+  // can't tell how the lifetimes of refs relate to each other.
+  //
+  // + dangle
+  // The dangle example from refs(), written as a fn:
+  //
+  // fn dangle() -> &String {
+  //   let s = String::from("hello");
+  //   &s
+  // }
+  //
+  // error[E0106]: missing lifetime specifier
+  //  --> src/main.rs:1:16
+  //   |
+  // 1 | fn dangle() -> &String {
+  //   |                ^ expected named lifetime parameter
+  //   |
+  //   = help: this function's return type contains a borrowed value, but there is no value for it to be borrowed from
+  //
+  // A returned ref has to borrow from *something* that outlives the call,
+  // and dangle takes no params to borrow from. The only thing left would
+  // be 'static, which is wrong too since s is dropped at the closing }.
+  // No lifetime can fix it, the answer is to hand back ownership
+  // instead, like no_dangle does.
+  println!("no_dangle -> {}", no_dangle("owned, nothing to dangle"));
+  println!();
+
+  // + longest<'a>
+  // Here there *are* params to borrow from, but which one? It depends on
+  // which string is longer, so without help the compiler can't say:
+  //
+  // fn longest(x: &str, y: &str) -> &str {
+  //
+  // error[E0106]: missing lifetime specifier
+  //   |
+  //   = help: this function's return type contains a borrowed value, but the signature does not say whether it is borrowed from `x` or `y`
+  //
+  // 'a is a lifetime parameter, like T is a type parameter. Putting it on
+  // both params and the result says: the result is valid for as long as
+  // *both* x and y are. It doesn't change how long anything lives, it
+  // only describes it so the caller can be checked.
+  fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+  }
+
+  let string1 = String::from("long string is long");
+  {
+    let string2 = String::from("xyz");
+    println!("longest -> {}", longest(string1.as_str(), string2.as_str()));
+  }
+  // Using the result after string2 is gone is what gets caught:
+  //
+  // let result;
+  // {
+  //   let string2 = String::from("xyz");
+  //   result = longest(string1.as_str(), string2.as_str());
+  // }
+  // println!("{}", result);
+  //
+  // error[E0597]: `string2` does not live long enough
+  //   |
+  //   |     let string2 = String::from("xyz");
+  //   |         ------- binding `string2` declared here
+  //   |     result = longest(string1.as_str(), string2.as_str());
+  //   |                                        ^^^^^^^ borrowed value does not live long enough
+  //   |   }
+  //   |   - `string2` dropped here while still borrowed
+  //   |   println!("{}", result);
+  //   |                  ------ borrow later used here
+  println!();
+
+  // + Structs holding refs
+  // A struct field that's a ref needs a lifetime too, otherwise it's the
+  // same E0106. Excerpt<'a> can't be used once the String it points into
+  // has been dropped.
+  let novel = String::from("Call me Ishmael. Some years ago...");
+  let first_sentence = novel.split('.').next().unwrap_or("");
+  let excerpt = Excerpt { part: first_sentence };
+  println!("excerpt -> {}", excerpt.announce("an excerpt"));
+  println!();
+
+  // + Elision
+  // Writing 'a on every fn that takes a ref would get old, so the
+  // compiler fills them in with three rules. If the rules don't settle
+  // the lifetime of the result, it's E0106.
+  //
+  // 1) Each ref param gets its own lifetime.
+  //      fn f(x: &str, y: &str)  is  fn f<'a, 'b>(x: &'a str, y: &'b str)
+  // 2) If there's exactly one input lifetime, the result gets it.
+  //      fn f(x: &str) -> &str  is  fn f<'a>(x: &'a str) -> &'a str
+  // 3) If one of the params is &self or &mut self, the result gets the
+  //    lifetime of self. That's why Excerpt::announce didn't need any.
+  //
+  // dangle has no inputs and longest has two, so neither is covered.
+  // calc_len in borrowing.rs returns a usize, nothing to borrow.
+  fn trim_dashes(s: &str) -> &str {
+    s.trim_matches('-')
+  }
+  println!("trim_dashes -> {}", trim_dashes("--rule two--"));
+  println!();
+
+  // + 'static
+  // 'static is the lifetime of the whole program. String literals have
+  // it, since the text is hardcoded into the binary (see moves_and_mem())
+  // and is never dropped. That's the one case where returning a ref with
+  // nothing to borrow from is fine:
+  fn greeting() -> &'static str {
+    "hello, from the binary"
+  }
+  let literal: &'static str = "also in the binary";
+  println!("{} / {}", greeting(), literal);
+  // It's what the compiler suggests for dangle too, but s is made at
+  // runtime and dropped at the end of the fn, so it could never be
+  // 'static.
+}
+
+fn slices() {
+  // [The Slice Type]
+  // Another data type that does not have ownership is the *slice*. Slices
+  // let you reference a contiguous sequence of elements in a collection
+  // rather than the whole collection.
+  //
+  // Say we want the first word of a string. Without slices, the best we
+  // could hand back is where the word ends:
+  fn first_word_end(s: &String) -> usize {
+    let bytes = s.as_bytes();
+    for (i, &item) in bytes.iter().enumerate() {
+      if item == b' ' {
+        return i;
+      }
+    }
+    s.len()
+  }
+
+  let mut s = String::from("hello world");
+  let end = first_word_end(&s);
+  s.clear();
+  // end is still 5, but there's no word anymore. Nothing ties the number
+  // to the String it came from, so it's on us to keep them in sync.
+  println!("first_word_end after clear -> {}, s is {:?}", end, s);
+
+  // + String slices
+  // A string slice is a reference to part of a String. It's written with
+  // a range of byte indices, [start..end), and is a pointer to the start
+  // plus a length (the fat pointer from layout()).
+  let s = String::from("hello world");
+  let hello = &s[0..5];
+  let world = &s[6..11];
+  println!("{} / {}", hello, world);
+  // Either end of the range can be left off, to start at 0 or run to the
+  // end. Leaving off both is a slice of the whole thing.
+  println!("{} / {} / {}", &s[..5], &s[6..], &s[..]);
+  // The indices are bytes, not chars, so they have to land between
+  // chars. See slice_boundaries()
+  //
+  // String literals are slices too, "hello" is a &'static str pointing
+  // at the text in the binary. That's why calc_len in borrowing.rs takes
+  // &str, it covers literals and slices of Strings both.
+  //
+  // + first_word, with slices
+  // Returning a slice ties the answer to the String. words.rs does the
+  // job properly, with words in any language and any kind of whitespace
+  // or punctuation between them.
+  let mut s = String::from("¿Dónde está la biblioteca? — ¡Aquí!");
+  let word = words::first_word(&s);
+  println!("first_word -> {}", word);
+  println!("nth_word(2) -> {:?}", words::nth_word(&s, 2));
+  println!("nth_word(9) -> {:?}", words::nth_word(&s, 9));
+  let all: Vec<&str> = words::words(&s).collect();
+  println!("words -> {:?}", all);
+  // Now clearing s while word still points into it doesn't compile:
+  //
+  // let word = first_word(&s);
+  // s.clear();
+  // println!("{}", word);
+  //
+  // error[E0502]: cannot borrow `s` as mutable because it is also borrowed as immutable
+  //   |
+  //   |     let word = first_word(&s);
+  //   |                           -- immutable borrow occurs here
+  //   |     s.clear();
+  //   |     ^^^^^^^^^ mutable borrow occurs here
+  //   |     println!("{}", word);
+  //   |                    ---- immutable borrow later used here
+  //
+  // clear needs a &mut, and word is a & that's still in use (refs()).
+  // Once it isn't, clearing is fine.
+  s.clear();
+  println!("first_word after clear -> {:?}", words::first_word(&s));
+  println!();
+
+  // Words in other scripts come out whole, marks and all. The tests in
+  // words.rs check every slice really is part of its input, on char
+  // boundaries.
+  for text in ["नमस्ते दुनिया", "ไม่ใช่", "e\u{301}cole", "3.14 1,000"].iter() {
+    let found: Vec<&str> = words::words(text).collect();
+    println!("{} -> {}", text, found.join(" | "));
+  }
+  println!();
+
+  // + Other slices
+  // Slices aren't only for strings. &[i32] is a slice of an array (or a
+  // Vec), with the same pointer and length.
+  let a = [1, 2, 3, 4, 5];
+  let middle: &[i32] = &a[1..3];
+  println!("{:?}", middle);
+}
+
+fn slice_boundaries() {
+  // A string slice's range is in *bytes*, and a String is UTF-8, where a
+  // char takes anywhere from 1 to 4 bytes. Slicing in the middle of one
+  // would make a &str that isn't valid UTF-8, so Rust panics instead.
+  use std::panic;
+
+  let hello = "hello";
+  let zdravstvuyte = "Здравствуйте";
+  println!("{:?} is {} chars, {} bytes", hello, hello.chars().count(), hello.len());
+  println!("{:?} is {} chars, {} bytes", zdravstvuyte, zdravstvuyte.chars().count(), zdravstvuyte.len());
+  println!("&hello[0..1] -> {}", &hello[0..1]);
+  println!("&zdravstvuyte[0..2] -> {}", &zdravstvuyte[0..2]);
+
+  // &zdravstvuyte[0..1] looks just as harmless, but З is two bytes and
+  // 1 is in the middle of it. catch_unwind stops the panic at the closure
+  // so we can look at it. The hook is swapped out while it runs so the
+  // panic doesn't print its own message on the way past.
+  let default_hook = panic::take_hook();
+  panic::set_hook(Box::new(|_| {}));
+  let result = panic::catch_unwind(|| {
+    let first = &zdravstvuyte[0..1];
+    first.len()
+  });
+  panic::set_hook(default_hook);
+
+  match result {
+    Ok(len) => println!("&zdravstvuyte[0..1] -> somehow {} bytes", len),
+    Err(payload) => {
+      // A panic carries whatever it was given, usually a String or &str.
+      let message = payload.downcast_ref::<String>().map(|s| s.as_str())
+        .or_else(|| payload.downcast_ref::<&str>().cloned())
+        .unwrap_or("<not a string>");
+      println!("&zdravstvuyte[0..1] panicked -> {}", message);
+    }
+  }
+  // The message says which char the index landed in and which bytes it
+  // covers, so the fix is to move the index to one end of it. The same
+  // thing happens with any index that isn't a boundary, at either end
+  // of the range. Catching the panic is only for showing it here, the
+  // real fix is to not slice blind. Some ways to do that:
+  println!();
+
+  // + get(..)
+  // The same as [..] but gives back None instead of panicking.
+  println!("get(0..1) -> {:?}", zdravstvuyte.get(0..1));
+  println!("get(0..2) -> {:?}", zdravstvuyte.get(0..2));
+  println!("get(0..99) -> {:?}", zdravstvuyte.get(0..99));
+
+  // + is_char_boundary
+  // Asks about an index directly. 0 and len() always are.
+  let boundaries: Vec<usize> = (0..=zdravstvuyte.len())
+    .filter(|&i| zdravstvuyte.is_char_boundary(i))
+    .collect();
+  println!("char boundaries -> {:?}", boundaries);
+  // Handy for cutting text down to at most some number of bytes: back
+  // up from the limit to the nearest boundary.
+  let mut cut = 5;
+  while !zdravstvuyte.is_char_boundary(cut) {
+    cut -= 1;
+  }
+  println!("at most 5 bytes -> {:?} ({} bytes)", &zdravstvuyte[..cut], cut);
+
+  // + char_indices
+  // Gives each char along with the byte index it starts at, so the
+  // indices are boundaries by construction. The first n chars end where
+  // char n starts, or at the end if there aren't that many.
+  fn first_chars(s: &str, n: usize) -> &str {
+    let end = s.char_indices().nth(n).map(|(i, _)| i).unwrap_or(s.len());
+    &s[..end]
+  }
+  println!("first_chars(3) -> {}", first_chars(zdravstvuyte, 3));
+  println!("first_chars(99) -> {}", first_chars(zdravstvuyte, 99));
+  // s.chars().take(3).collect::<String>() gets the same text, but copies
+  // it into a new String. The slice just points into the old one.
+  //
+  // This is the same trick words.rs uses, which is why it never panics.
+  //
+  // One last catch: a char isn't always what a person would call a
+  // character. "é" can be one char or an e plus a combining accent, and
+  // then first_chars(1) cuts the accent off:
+  let decomposed = "e\u{301}cole";
+  println!("{} -> first_chars(1) -> {:?}", decomposed, first_chars(decomposed, 1));
+  // No panic, the slice is still valid UTF-8, it's just not the whole
+  // letter. Getting that right needs the Unicode rules for grapheme
+  // clusters, which are outside of std.
 }
 
 fn takes_ownership(some_string: String) { // some_string comes into scope
@@ -504,6 +1250,20 @@ fn gives_ownership() -> String { // Gives ownership will move its return
   derp // derp is returned and moves out to the calling function
 }
 
+// Gives the String back along with its length, so the caller can keep
+// using it.
+fn calculate_length(s: String) -> (String, usize) {
+  let length = s.len();
+  (s, length)
+}
+
+// Takes &String the way the book does. See refs() for why calc_len in
+// borrowing.rs takes a &str instead.
+#[allow(clippy::ptr_arg)]
+fn calculate_length_borrowed(s: &String) -> usize {
+  s.len()
+}
+
 // takes_and_gives_back will take a String and return one
 fn takes_and_gives_back(foo: String) -> String { // foo comes into scope
   foo // foo is returned and moves out to the calling fn
@@ -0,0 +1,447 @@
+////////////////////////////////////////////////////////////////////////////////
+// Words
+////////////////////////////////////////////////////////////////////////////////
+// first_word from slices(), grown up: the first word, the nth word, or
+// all of them, each handed back as a &str pointing into the input.
+//
+// A word is a run of letters and digits in any script (char's
+// is_alphanumeric, which knows about more than ASCII). It can carry:
+// + combining marks (Unicode's Mn, Mc and Me), in any script. That's the
+//   accent in "é" written as e + U+0301, the virama and vowel signs in
+//   "नमस्ते", the Thai tone marks in "ไม่ใช่" and Hebrew points and
+//   cantillation. std can't tell us what's a mark, so MARKS lists them.
+// + an apostrophe, hyphen or zero width (non-)joiner *between* two
+//   letters or digits, so "don't", "l'été", "well-known" and "می‌خواهم"
+//   stay whole but "'quoted'" loses its quotes.
+// + a `.` or `,` between two digits, so "3.14" and "1,000" are one word.
+// Anything else, whitespace of any kind, punctuation, symbols and emoji,
+// sits between words.
+//
+// Every index used for slicing comes out of char_indices(), so it's
+// always on a char boundary and nothing in here can panic on non-ASCII
+// text.
+////////////////////////////////////////////////////////////////////////////////
+use std::cmp::Ordering;
+
+/// All the words in `text`, in order.
+///
+/// ```
+/// use c4_ownership::words::words;
+///
+/// let found: Vec<&str> = words("¿Qué tal? «Très bien», don't-stop!").collect();
+/// assert_eq!(found, ["Qué", "tal", "Très", "bien", "don't-stop"]);
+/// ```
+pub fn words(text: &str) -> Words<'_> {
+  Words { text, pos: 0 }
+}
+
+/// The first word in `text`, or `""` if there isn't one.
+///
+/// ```
+/// use c4_ownership::words::first_word;
+///
+/// assert_eq!(first_word("hello world"), "hello");
+/// assert_eq!(first_word("  \u{3000}—¡Hola!"), "Hola");
+/// assert_eq!(first_word("... !!"), "");
+/// ```
+pub fn first_word(text: &str) -> &str {
+  // The empty slice at the end, so even "no word" points into text.
+  words(text).next().unwrap_or(&text[text.len()..])
+}
+
+/// The word at `n`, counting from 0, if there are that many.
+///
+/// ```
+/// use c4_ownership::words::nth_word;
+///
+/// assert_eq!(nth_word("один два три", 1), Some("два"));
+/// assert_eq!(nth_word("один два три", 3), None);
+/// ```
+pub fn nth_word(text: &str, n: usize) -> Option<&str> {
+  words(text).nth(n)
+}
+
+pub struct Words<'a> {
+  text: &'a str,
+  // Everything before pos has already been handed out or skipped.
+  pos: usize,
+}
+
+impl<'a> Iterator for Words<'a> {
+  type Item = &'a str;
+
+  fn next(&mut self) -> Option<&'a str> {
+    let rest = &self.text[self.pos..];
+    let mut chars = rest.char_indices().peekable();
+
+    let (start, mut prev) = loop {
+      match chars.next() {
+        Some((i, c)) if c.is_alphanumeric() => break (i, c),
+        Some(_) => continue,
+        None => {
+          self.pos = self.text.len();
+          return None;
+        }
+      }
+    };
+
+    let mut end = rest.len();
+    while let Some((i, c)) = chars.next() {
+      if c.is_alphanumeric() {
+        prev = c;
+        continue;
+      }
+      if is_mark(c) {
+        continue;
+      }
+      if joins(prev, c, chars.peek().map(|&(_, next)| next)) {
+        continue;
+      }
+      end = i;
+      break;
+    }
+
+    self.pos += end;
+    Some(&rest[start..end])
+  }
+}
+
+// Whether c keeps the word going, given the last letter or digit before
+// it and the char after it.
+fn joins(prev: char, c: char, next: Option<char>) -> bool {
+  let next = match next {
+    Some(next) => next,
+    None => return false,
+  };
+  match c {
+    '\'' | '’' | '-' | '‐' | '\u{200C}' | '\u{200D}' => next.is_alphanumeric(),
+    '.' | ',' => prev.is_numeric() && next.is_numeric(),
+    _ => false,
+  }
+}
+
+fn is_mark(c: char) -> bool {
+  // Nothing below the first block of combining marks is one.
+  if c < '\u{0300}' {
+    return false;
+  }
+  MARKS.binary_search_by(|&(lo, hi)| {
+    if hi < c {
+      Ordering::Less
+    } else if lo > c {
+      Ordering::Greater
+    } else {
+      Ordering::Equal
+    }
+  }).is_ok()
+}
+
+// Every char in general category Mn, Mc or Me, as sorted inclusive
+// ranges. Generated with Python 3's unicodedata (Unicode 14.0.0), by
+// pasting in the output of:
+//
+//   import unicodedata
+//   r = []
+//   for cp in range(0x110000):
+//     if unicodedata.category(chr(cp)) in ('Mn', 'Mc', 'Me'):
+//       if r and r[-1][1] == cp - 1: r[-1][1] = cp
+//       else: r.append([cp, cp])
+//   line = ' '
+//   for lo, hi in r:
+//     e = " ('\\u{%04X}', '\\u{%04X}')," % (lo, hi)
+//     if len(line) + len(e) > 80: print(line); line = ' '
+//     line += e
+//   print(line)
+//
+// That's older than the Unicode std's is_alphanumeric follows (see
+// char::UNICODE_VERSION, 17.0 as of Rust 1.95). Letters added since are
+// still words, but marks added since aren't in here, so they split the
+// word they're in: U+0ECE LAO YAMAKKAN (new in 15.0) is one. Rerun the
+// script under a newer Python to catch up.
+const MARKS: &[(char, char)] = &[
+  ('\u{0300}', '\u{036F}'), ('\u{0483}', '\u{0489}'), ('\u{0591}', '\u{05BD}'),
+  ('\u{05BF}', '\u{05BF}'), ('\u{05C1}', '\u{05C2}'), ('\u{05C4}', '\u{05C5}'),
+  ('\u{05C7}', '\u{05C7}'), ('\u{0610}', '\u{061A}'), ('\u{064B}', '\u{065F}'),
+  ('\u{0670}', '\u{0670}'), ('\u{06D6}', '\u{06DC}'), ('\u{06DF}', '\u{06E4}'),
+  ('\u{06E7}', '\u{06E8}'), ('\u{06EA}', '\u{06ED}'), ('\u{0711}', '\u{0711}'),
+  ('\u{0730}', '\u{074A}'), ('\u{07A6}', '\u{07B0}'), ('\u{07EB}', '\u{07F3}'),
+  ('\u{07FD}', '\u{07FD}'), ('\u{0816}', '\u{0819}'), ('\u{081B}', '\u{0823}'),
+  ('\u{0825}', '\u{0827}'), ('\u{0829}', '\u{082D}'), ('\u{0859}', '\u{085B}'),
+  ('\u{0898}', '\u{089F}'), ('\u{08CA}', '\u{08E1}'), ('\u{08E3}', '\u{0903}'),
+  ('\u{093A}', '\u{093C}'), ('\u{093E}', '\u{094F}'), ('\u{0951}', '\u{0957}'),
+  ('\u{0962}', '\u{0963}'), ('\u{0981}', '\u{0983}'), ('\u{09BC}', '\u{09BC}'),
+  ('\u{09BE}', '\u{09C4}'), ('\u{09C7}', '\u{09C8}'), ('\u{09CB}', '\u{09CD}'),
+  ('\u{09D7}', '\u{09D7}'), ('\u{09E2}', '\u{09E3}'), ('\u{09FE}', '\u{09FE}'),
+  ('\u{0A01}', '\u{0A03}'), ('\u{0A3C}', '\u{0A3C}'), ('\u{0A3E}', '\u{0A42}'),
+  ('\u{0A47}', '\u{0A48}'), ('\u{0A4B}', '\u{0A4D}'), ('\u{0A51}', '\u{0A51}'),
+  ('\u{0A70}', '\u{0A71}'), ('\u{0A75}', '\u{0A75}'), ('\u{0A81}', '\u{0A83}'),
+  ('\u{0ABC}', '\u{0ABC}'), ('\u{0ABE}', '\u{0AC5}'), ('\u{0AC7}', '\u{0AC9}'),
+  ('\u{0ACB}', '\u{0ACD}'), ('\u{0AE2}', '\u{0AE3}'), ('\u{0AFA}', '\u{0AFF}'),
+  ('\u{0B01}', '\u{0B03}'), ('\u{0B3C}', '\u{0B3C}'), ('\u{0B3E}', '\u{0B44}'),
+  ('\u{0B47}', '\u{0B48}'), ('\u{0B4B}', '\u{0B4D}'), ('\u{0B55}', '\u{0B57}'),
+  ('\u{0B62}', '\u{0B63}'), ('\u{0B82}', '\u{0B82}'), ('\u{0BBE}', '\u{0BC2}'),
+  ('\u{0BC6}', '\u{0BC8}'), ('\u{0BCA}', '\u{0BCD}'), ('\u{0BD7}', '\u{0BD7}'),
+  ('\u{0C00}', '\u{0C04}'), ('\u{0C3C}', '\u{0C3C}'), ('\u{0C3E}', '\u{0C44}'),
+  ('\u{0C46}', '\u{0C48}'), ('\u{0C4A}', '\u{0C4D}'), ('\u{0C55}', '\u{0C56}'),
+  ('\u{0C62}', '\u{0C63}'), ('\u{0C81}', '\u{0C83}'), ('\u{0CBC}', '\u{0CBC}'),
+  ('\u{0CBE}', '\u{0CC4}'), ('\u{0CC6}', '\u{0CC8}'), ('\u{0CCA}', '\u{0CCD}'),
+  ('\u{0CD5}', '\u{0CD6}'), ('\u{0CE2}', '\u{0CE3}'), ('\u{0D00}', '\u{0D03}'),
+  ('\u{0D3B}', '\u{0D3C}'), ('\u{0D3E}', '\u{0D44}'), ('\u{0D46}', '\u{0D48}'),
+  ('\u{0D4A}', '\u{0D4D}'), ('\u{0D57}', '\u{0D57}'), ('\u{0D62}', '\u{0D63}'),
+  ('\u{0D81}', '\u{0D83}'), ('\u{0DCA}', '\u{0DCA}'), ('\u{0DCF}', '\u{0DD4}'),
+  ('\u{0DD6}', '\u{0DD6}'), ('\u{0DD8}', '\u{0DDF}'), ('\u{0DF2}', '\u{0DF3}'),
+  ('\u{0E31}', '\u{0E31}'), ('\u{0E34}', '\u{0E3A}'), ('\u{0E47}', '\u{0E4E}'),
+  ('\u{0EB1}', '\u{0EB1}'), ('\u{0EB4}', '\u{0EBC}'), ('\u{0EC8}', '\u{0ECD}'),
+  ('\u{0F18}', '\u{0F19}'), ('\u{0F35}', '\u{0F35}'), ('\u{0F37}', '\u{0F37}'),
+  ('\u{0F39}', '\u{0F39}'), ('\u{0F3E}', '\u{0F3F}'), ('\u{0F71}', '\u{0F84}'),
+  ('\u{0F86}', '\u{0F87}'), ('\u{0F8D}', '\u{0F97}'), ('\u{0F99}', '\u{0FBC}'),
+  ('\u{0FC6}', '\u{0FC6}'), ('\u{102B}', '\u{103E}'), ('\u{1056}', '\u{1059}'),
+  ('\u{105E}', '\u{1060}'), ('\u{1062}', '\u{1064}'), ('\u{1067}', '\u{106D}'),
+  ('\u{1071}', '\u{1074}'), ('\u{1082}', '\u{108D}'), ('\u{108F}', '\u{108F}'),
+  ('\u{109A}', '\u{109D}'), ('\u{135D}', '\u{135F}'), ('\u{1712}', '\u{1715}'),
+  ('\u{1732}', '\u{1734}'), ('\u{1752}', '\u{1753}'), ('\u{1772}', '\u{1773}'),
+  ('\u{17B4}', '\u{17D3}'), ('\u{17DD}', '\u{17DD}'), ('\u{180B}', '\u{180D}'),
+  ('\u{180F}', '\u{180F}'), ('\u{1885}', '\u{1886}'), ('\u{18A9}', '\u{18A9}'),
+  ('\u{1920}', '\u{192B}'), ('\u{1930}', '\u{193B}'), ('\u{1A17}', '\u{1A1B}'),
+  ('\u{1A55}', '\u{1A5E}'), ('\u{1A60}', '\u{1A7C}'), ('\u{1A7F}', '\u{1A7F}'),
+  ('\u{1AB0}', '\u{1ACE}'), ('\u{1B00}', '\u{1B04}'), ('\u{1B34}', '\u{1B44}'),
+  ('\u{1B6B}', '\u{1B73}'), ('\u{1B80}', '\u{1B82}'), ('\u{1BA1}', '\u{1BAD}'),
+  ('\u{1BE6}', '\u{1BF3}'), ('\u{1C24}', '\u{1C37}'), ('\u{1CD0}', '\u{1CD2}'),
+  ('\u{1CD4}', '\u{1CE8}'), ('\u{1CED}', '\u{1CED}'), ('\u{1CF4}', '\u{1CF4}'),
+  ('\u{1CF7}', '\u{1CF9}'), ('\u{1DC0}', '\u{1DFF}'), ('\u{20D0}', '\u{20F0}'),
+  ('\u{2CEF}', '\u{2CF1}'), ('\u{2D7F}', '\u{2D7F}'), ('\u{2DE0}', '\u{2DFF}'),
+  ('\u{302A}', '\u{302F}'), ('\u{3099}', '\u{309A}'), ('\u{A66F}', '\u{A672}'),
+  ('\u{A674}', '\u{A67D}'), ('\u{A69E}', '\u{A69F}'), ('\u{A6F0}', '\u{A6F1}'),
+  ('\u{A802}', '\u{A802}'), ('\u{A806}', '\u{A806}'), ('\u{A80B}', '\u{A80B}'),
+  ('\u{A823}', '\u{A827}'), ('\u{A82C}', '\u{A82C}'), ('\u{A880}', '\u{A881}'),
+  ('\u{A8B4}', '\u{A8C5}'), ('\u{A8E0}', '\u{A8F1}'), ('\u{A8FF}', '\u{A8FF}'),
+  ('\u{A926}', '\u{A92D}'), ('\u{A947}', '\u{A953}'), ('\u{A980}', '\u{A983}'),
+  ('\u{A9B3}', '\u{A9C0}'), ('\u{A9E5}', '\u{A9E5}'), ('\u{AA29}', '\u{AA36}'),
+  ('\u{AA43}', '\u{AA43}'), ('\u{AA4C}', '\u{AA4D}'), ('\u{AA7B}', '\u{AA7D}'),
+  ('\u{AAB0}', '\u{AAB0}'), ('\u{AAB2}', '\u{AAB4}'), ('\u{AAB7}', '\u{AAB8}'),
+  ('\u{AABE}', '\u{AABF}'), ('\u{AAC1}', '\u{AAC1}'), ('\u{AAEB}', '\u{AAEF}'),
+  ('\u{AAF5}', '\u{AAF6}'), ('\u{ABE3}', '\u{ABEA}'), ('\u{ABEC}', '\u{ABED}'),
+  ('\u{FB1E}', '\u{FB1E}'), ('\u{FE00}', '\u{FE0F}'), ('\u{FE20}', '\u{FE2F}'),
+  ('\u{101FD}', '\u{101FD}'), ('\u{102E0}', '\u{102E0}'),
+  ('\u{10376}', '\u{1037A}'), ('\u{10A01}', '\u{10A03}'),
+  ('\u{10A05}', '\u{10A06}'), ('\u{10A0C}', '\u{10A0F}'),
+  ('\u{10A38}', '\u{10A3A}'), ('\u{10A3F}', '\u{10A3F}'),
+  ('\u{10AE5}', '\u{10AE6}'), ('\u{10D24}', '\u{10D27}'),
+  ('\u{10EAB}', '\u{10EAC}'), ('\u{10F46}', '\u{10F50}'),
+  ('\u{10F82}', '\u{10F85}'), ('\u{11000}', '\u{11002}'),
+  ('\u{11038}', '\u{11046}'), ('\u{11070}', '\u{11070}'),
+  ('\u{11073}', '\u{11074}'), ('\u{1107F}', '\u{11082}'),
+  ('\u{110B0}', '\u{110BA}'), ('\u{110C2}', '\u{110C2}'),
+  ('\u{11100}', '\u{11102}'), ('\u{11127}', '\u{11134}'),
+  ('\u{11145}', '\u{11146}'), ('\u{11173}', '\u{11173}'),
+  ('\u{11180}', '\u{11182}'), ('\u{111B3}', '\u{111C0}'),
+  ('\u{111C9}', '\u{111CC}'), ('\u{111CE}', '\u{111CF}'),
+  ('\u{1122C}', '\u{11237}'), ('\u{1123E}', '\u{1123E}'),
+  ('\u{112DF}', '\u{112EA}'), ('\u{11300}', '\u{11303}'),
+  ('\u{1133B}', '\u{1133C}'), ('\u{1133E}', '\u{11344}'),
+  ('\u{11347}', '\u{11348}'), ('\u{1134B}', '\u{1134D}'),
+  ('\u{11357}', '\u{11357}'), ('\u{11362}', '\u{11363}'),
+  ('\u{11366}', '\u{1136C}'), ('\u{11370}', '\u{11374}'),
+  ('\u{11435}', '\u{11446}'), ('\u{1145E}', '\u{1145E}'),
+  ('\u{114B0}', '\u{114C3}'), ('\u{115AF}', '\u{115B5}'),
+  ('\u{115B8}', '\u{115C0}'), ('\u{115DC}', '\u{115DD}'),
+  ('\u{11630}', '\u{11640}'), ('\u{116AB}', '\u{116B7}'),
+  ('\u{1171D}', '\u{1172B}'), ('\u{1182C}', '\u{1183A}'),
+  ('\u{11930}', '\u{11935}'), ('\u{11937}', '\u{11938}'),
+  ('\u{1193B}', '\u{1193E}'), ('\u{11940}', '\u{11940}'),
+  ('\u{11942}', '\u{11943}'), ('\u{119D1}', '\u{119D7}'),
+  ('\u{119DA}', '\u{119E0}'), ('\u{119E4}', '\u{119E4}'),
+  ('\u{11A01}', '\u{11A0A}'), ('\u{11A33}', '\u{11A39}'),
+  ('\u{11A3B}', '\u{11A3E}'), ('\u{11A47}', '\u{11A47}'),
+  ('\u{11A51}', '\u{11A5B}'), ('\u{11A8A}', '\u{11A99}'),
+  ('\u{11C2F}', '\u{11C36}'), ('\u{11C38}', '\u{11C3F}'),
+  ('\u{11C92}', '\u{11CA7}'), ('\u{11CA9}', '\u{11CB6}'),
+  ('\u{11D31}', '\u{11D36}'), ('\u{11D3A}', '\u{11D3A}'),
+  ('\u{11D3C}', '\u{11D3D}'), ('\u{11D3F}', '\u{11D45}'),
+  ('\u{11D47}', '\u{11D47}'), ('\u{11D8A}', '\u{11D8E}'),
+  ('\u{11D90}', '\u{11D91}'), ('\u{11D93}', '\u{11D97}'),
+  ('\u{11EF3}', '\u{11EF6}'), ('\u{16AF0}', '\u{16AF4}'),
+  ('\u{16B30}', '\u{16B36}'), ('\u{16F4F}', '\u{16F4F}'),
+  ('\u{16F51}', '\u{16F87}'), ('\u{16F8F}', '\u{16F92}'),
+  ('\u{16FE4}', '\u{16FE4}'), ('\u{16FF0}', '\u{16FF1}'),
+  ('\u{1BC9D}', '\u{1BC9E}'), ('\u{1CF00}', '\u{1CF2D}'),
+  ('\u{1CF30}', '\u{1CF46}'), ('\u{1D165}', '\u{1D169}'),
+  ('\u{1D16D}', '\u{1D172}'), ('\u{1D17B}', '\u{1D182}'),
+  ('\u{1D185}', '\u{1D18B}'), ('\u{1D1AA}', '\u{1D1AD}'),
+  ('\u{1D242}', '\u{1D244}'), ('\u{1DA00}', '\u{1DA36}'),
+  ('\u{1DA3B}', '\u{1DA6C}'), ('\u{1DA75}', '\u{1DA75}'),
+  ('\u{1DA84}', '\u{1DA84}'), ('\u{1DA9B}', '\u{1DA9F}'),
+  ('\u{1DAA1}', '\u{1DAAF}'), ('\u{1E000}', '\u{1E006}'),
+  ('\u{1E008}', '\u{1E018}'), ('\u{1E01B}', '\u{1E021}'),
+  ('\u{1E023}', '\u{1E024}'), ('\u{1E026}', '\u{1E02A}'),
+  ('\u{1E130}', '\u{1E136}'), ('\u{1E2AE}', '\u{1E2AE}'),
+  ('\u{1E2EC}', '\u{1E2EF}'), ('\u{1E8D0}', '\u{1E8D6}'),
+  ('\u{1E944}', '\u{1E94A}'), ('\u{E0100}', '\u{E01EF}'),
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Checks everything the functions above promise about text, giving
+  // back what went wrong if anything did:
+  // + every word is a non-empty slice of text, starting and ending on
+  //   char boundaries, that starts with a letter or digit
+  // + the words come in order and don't overlap
+  // + no word holds whitespace
+  // + every word is as long as it can be: it isn't preceded by a letter
+  //   or digit, or followed by one or by a mark
+  // + first_word and nth_word agree with words
+  fn check(text: &str) -> Result<(), String> {
+    let start_of_text = text.as_ptr() as usize;
+    let mut last_end = 0;
+    let mut count = 0;
+
+    for (n, word) in words(text).enumerate() {
+      // Where the slice sits in text, worked out from the pointers since
+      // that's the only way to be sure it really points into text.
+      let start = (word.as_ptr() as usize).wrapping_sub(start_of_text);
+      let end = start + word.len();
+      if start > text.len() || end > text.len() {
+        return Err(format!("word {} {:?} isn't inside the text", n, word));
+      }
+      if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+        return Err(format!("word {} {:?} isn't on char boundaries", n, word));
+      }
+      if text.get(start..end) != Some(word) {
+        return Err(format!("word {} {:?} doesn't match the text at {}..{}", n, word, start, end));
+      }
+      if !word.chars().next().is_some_and(char::is_alphanumeric) {
+        return Err(format!("word {} {:?} doesn't start with a letter or digit", n, word));
+      }
+      if word.chars().any(char::is_whitespace) {
+        return Err(format!("word {} {:?} has whitespace in it", n, word));
+      }
+      if start < last_end {
+        return Err(format!("word {} {:?} overlaps the one before it", n, word));
+      }
+      if text[..start].chars().next_back().is_some_and(char::is_alphanumeric) {
+        return Err(format!("word {} {:?} starts in the middle of a word", n, word));
+      }
+      if let Some(after) = text[end..].chars().next() {
+        if after.is_alphanumeric() || is_mark(after) {
+          return Err(format!("word {} {:?} stops before {:?}", n, word, after));
+        }
+      }
+      if nth_word(text, n) != Some(word) {
+        return Err(format!("nth_word({}) is {:?}, words gave {:?}", n, nth_word(text, n), word));
+      }
+      last_end = end;
+      count += 1;
+    }
+
+    let expected_first = words(text).next().unwrap_or("");
+    if first_word(text) != expected_first {
+      return Err(format!("first_word is {:?}, words gave {:?}", first_word(text), expected_first));
+    }
+    if nth_word(text, count).is_some() {
+      return Err(format!("nth_word({}) found a word past the last one", count));
+    }
+    Ok(())
+  }
+
+  // Letters from a few scripts, marks, joiners, punctuation and every
+  // kind of space, in one byte to four.
+  const SAMPLE_CHARS: &[char] = &[
+    'a', 'Z', '7', '٣', 'é', 'ß', 'ж', 'λ', 'ي', 'ی', 'क', 'न', 'त', 'ि', '्', 'े',
+    'ไ', 'ม', '่', 'ั', 'ש', 'ב', 'ָ', '\u{0591}', '中', '文', 'ア', '한',
+    '\u{0301}', '\u{0308}', '\u{20DD}', '\'', '’', '-', '‐', '\u{200C}', '\u{200D}',
+    '.', ',', '!', '?', '¿', '¡', '«', '»', '—', '…', '。', '、', '"', '(', ')',
+    ' ', '\t', '\n', '\u{A0}', '\u{2009}', '\u{3000}', '\u{1680}', '\u{2028}', '\u{85}', '\u{200B}',
+    '😀', '👍', '\u{1F3FD}', '𝔘', '𝟘', '\u{10FFFF}',
+  ];
+
+  // A made up string of len chars from SAMPLE_CHARS, the same one every
+  // time for the same seed.
+  fn sample(seed: u64, len: usize) -> String {
+    // xorshift, plenty random enough to pick chars with.
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..len)
+      .map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        SAMPLE_CHARS[(state % SAMPLE_CHARS.len() as u64) as usize]
+      })
+      .collect()
+  }
+
+  fn all(text: &str) -> Vec<&str> {
+    words(text).collect()
+  }
+
+  #[test]
+  fn made_up_strings_hold_up() {
+    for seed in 0..10_000 {
+      let text = sample(seed, 40);
+      assert_eq!(check(&text), Ok(()), "seed {} {:?}", seed, text);
+    }
+  }
+
+  #[test]
+  fn awkward_strings_hold_up() {
+    let awkward = [
+      "",
+      "   ",
+      "hello",
+      "e\u{301}cole\u{301}",
+      "l'été, c'est-à-dire",
+      "'quoted' -dash- don't",
+      "naïve\u{A0}café\u{3000}東京\u{2028}Ελλάδα",
+      "👍🏽 emoji 😀 between\u{200D}words",
+      "\u{301}starts with a mark",
+      "3.14 1,000 end. 3.",
+    ];
+    for text in awkward.iter() {
+      assert_eq!(check(text), Ok(()), "{:?}", text);
+    }
+  }
+
+  #[test]
+  fn latin() {
+    assert_eq!(all("e\u{301}cole\u{301}"), ["e\u{301}cole\u{301}"]);
+    assert_eq!(all("'quoted' -dash- don't"), ["quoted", "dash", "don't"]);
+    assert_eq!(all("l'été, c'est-à-dire"), ["l'été", "c'est-à-dire"]);
+  }
+
+  #[test]
+  fn devanagari_keeps_its_virama_and_vowel_signs() {
+    assert_eq!(all("नमस्ते दुनिया"), ["नमस्ते", "दुनिया"]);
+  }
+
+  #[test]
+  fn thai_keeps_its_tone_marks() {
+    assert_eq!(all("ไม่ใช่"), ["ไม่ใช่"]);
+    assert_eq!(all("ไม่ ใช่"), ["ไม่", "ใช่"]);
+  }
+
+  #[test]
+  fn hebrew_keeps_points_and_cantillation() {
+    let text = "בְּרֵאשִׁ֖ית בָּרָ֣א";
+    assert_eq!(all(text), ["בְּרֵאשִׁ֖ית", "בָּרָ֣א"]);
+  }
+
+  #[test]
+  fn zero_width_non_joiner_inside_a_word() {
+    assert_eq!(all("می‌خواهم بروم"), ["می‌خواهم", "بروم"]);
+  }
+
+  #[test]
+  fn numbers_keep_their_separators() {
+    assert_eq!(all("3.14 1,000"), ["3.14", "1,000"]);
+    assert_eq!(all("end. 3. a.b 1,a"), ["end", "3", "a", "b", "1", "a"]);
+  }
+
+  #[test]
+  fn nothing_to_find() {
+    assert_eq!(first_word(""), "");
+    assert_eq!(first_word("¿¡… — 👍"), "");
+    assert_eq!(nth_word("", 0), None);
+    assert_eq!(nth_word("\u{301}\u{94D}", 0), None);
+  }
+}